@@ -0,0 +1,42 @@
+//! Result codes for the BLS12-381 codec and aggregate-verify entry points.
+//!
+//! Unlike [`crate::bn254_code::Bn254Code`], nothing in this crate branches
+//! on a *category* of BLS failure — there's no BLS equivalent of the EVMC
+//! dispatcher in `evmc.rs` that maps failure categories onto status codes —
+//! so packing a group byte alongside each code would just be unused
+//! machinery. This is a plain set of codes, still fitting the existing
+//! `c_int` C ABI via [`Bls12381Code::raw`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bls12381Code(u16);
+
+impl Bls12381Code {
+    pub const SUCCESS: Bls12381Code = Bls12381Code(0);
+
+    /// The input pointer was null.
+    pub const NULL_POINTER: Bls12381Code = Bls12381Code(1);
+    /// The input's length doesn't match what the call expects.
+    pub const INPUT_LENGTH_MISMATCH: Bls12381Code = Bls12381Code(2);
+
+    /// A compressed encoding's flag bits are malformed.
+    pub const BAD_COMPRESSION_FLAG: Bls12381Code = Bls12381Code(3);
+    /// The infinity flag is set but the rest of the encoding isn't the
+    /// canonical all-zero infinity representation (or vice versa).
+    pub const BAD_INFINITY_ENCODING: Bls12381Code = Bls12381Code(4);
+    /// A coordinate is out of the base field's range.
+    pub const COORDINATE_OUT_OF_RANGE: Bls12381Code = Bls12381Code(5);
+
+    /// A point does not satisfy the curve equation.
+    pub const NOT_ON_CURVE: Bls12381Code = Bls12381Code(6);
+    /// A point is on the curve but outside the correct subgroup.
+    pub const NOT_IN_SUBGROUP: Bls12381Code = Bls12381Code(7);
+
+    /// An aggregate-verify call's pubkey count doesn't match its message
+    /// count.
+    pub const PUBKEY_MESSAGE_COUNT_MISMATCH: Bls12381Code = Bls12381Code(8);
+
+    /// The raw `c_int`-compatible value used for the C ABI and wire
+    /// encoding.
+    pub fn raw(&self) -> u16 {
+        self.0
+    }
+}
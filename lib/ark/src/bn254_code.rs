@@ -0,0 +1,90 @@
+//! Structured result codes for the BN254 validation entry points.
+//!
+//! `Bn254Result` collapses every validation failure into a single
+//! `InvalidInput` variant, so a caller can't distinguish "length not a
+//! multiple of 192" from "coordinate >= field modulus" from "point not on
+//! curve". `Bn254Code` packs a group byte (the failure category) and a
+//! unique byte (the specific failure) into a `u16` so callers can branch on
+//! the category without string parsing, while still fitting the existing
+//! `c_int` C ABI via [`Bn254Code::raw`].
+
+/// High byte: the category of failure. Low byte: which specific check
+/// within that category failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bn254Code {
+    group_id: u8,
+    unique_id: u8,
+}
+
+impl Bn254Code {
+    /// Group 0: no failure.
+    pub const GROUP_SUCCESS: u8 = 0;
+    /// Group 1: the input's shape is wrong (null pointer, bad length).
+    pub const GROUP_INPUT_SHAPE: u8 = 1;
+    /// Group 2: a coordinate is out of the base field's range.
+    pub const GROUP_FIELD_RANGE: u8 = 2;
+    /// Group 3: a point does not satisfy the curve equation.
+    pub const GROUP_CURVE_MEMBERSHIP: u8 = 3;
+    /// Group 4: a point is on the curve but outside the correct subgroup.
+    pub const GROUP_SUBGROUP: u8 = 4;
+
+    pub const SUCCESS: Bn254Code = Bn254Code::new(Self::GROUP_SUCCESS, 0);
+
+    pub const NULL_POINTER: Bn254Code = Bn254Code::new(Self::GROUP_INPUT_SHAPE, 1);
+    pub const ECMUL_INPUT_TOO_SHORT: Bn254Code = Bn254Code::new(Self::GROUP_INPUT_SHAPE, 2);
+    pub const ECPAIRING_LENGTH_NOT_MULTIPLE_OF_192: Bn254Code =
+        Bn254Code::new(Self::GROUP_INPUT_SHAPE, 3);
+
+    pub const G1_X_OUT_OF_RANGE: Bn254Code = Bn254Code::new(Self::GROUP_FIELD_RANGE, 1);
+    pub const G1_Y_OUT_OF_RANGE: Bn254Code = Bn254Code::new(Self::GROUP_FIELD_RANGE, 2);
+    pub const G2_COORDINATE_OUT_OF_RANGE: Bn254Code = Bn254Code::new(Self::GROUP_FIELD_RANGE, 3);
+
+    pub const G1_NOT_ON_CURVE: Bn254Code = Bn254Code::new(Self::GROUP_CURVE_MEMBERSHIP, 1);
+    pub const G2_NOT_ON_CURVE: Bn254Code = Bn254Code::new(Self::GROUP_CURVE_MEMBERSHIP, 2);
+
+    pub const G2_NOT_IN_SUBGROUP: Bn254Code = Bn254Code::new(Self::GROUP_SUBGROUP, 1);
+
+    const fn new(group_id: u8, unique_id: u8) -> Self {
+        Self { group_id, unique_id }
+    }
+
+    pub fn group_id(&self) -> u8 {
+        self.group_id
+    }
+
+    pub fn unique_id(&self) -> u8 {
+        self.unique_id
+    }
+
+    /// Pack into the high byte (group) / low byte (unique) `u16` used for
+    /// the C ABI and wire encoding.
+    pub fn raw(&self) -> u16 {
+        ((self.group_id as u16) << 8) | self.unique_id() as u16
+    }
+
+    /// Unpack a `u16` produced by [`Self::raw`].
+    pub fn from(raw: u16) -> Self {
+        Self {
+            group_id: (raw >> 8) as u8,
+            unique_id: (raw & 0xff) as u8,
+        }
+    }
+
+    /// Decode a code written by [`Self::write_to_be_bytes`]. `evmc.rs`'s
+    /// dispatcher writes the specific code into the caller's output buffer
+    /// on a validation failure, so a caller that wants more than the coarse
+    /// EVMC status code can decode it with this to recover exactly which
+    /// check failed.
+    pub fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        Self::from(u16::from_be_bytes(bytes))
+    }
+
+    /// Encode for a byte buffer; see [`Self::from_be_bytes`].
+    pub fn write_to_be_bytes(&self, out: &mut [u8; 2]) {
+        *out = self.raw().to_be_bytes();
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.group_id == Self::GROUP_SUCCESS
+    }
+}
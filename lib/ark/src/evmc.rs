@@ -0,0 +1,323 @@
+//! EVMC-style dispatch surface over the BN254 FFI.
+//!
+//! Wraps the existing `bn254_ecmul`/`bn254_ecpairing` entry points behind
+//! the connector-API convention EVMC-compatible clients expect: a single
+//! `execute` call that takes a precompile address and an input buffer, and
+//! returns a fixed-layout result carrying a status code, the output slice,
+//! and the remaining gas. Internally this reuses each precompile's
+//! `*_validate_input`/`*_output_size` helpers, so the dispatcher pre-sizes
+//! the output buffer and maps structured validation codes onto EVMC status
+//! codes instead of re-deriving either.
+
+use crate::bn254_code::Bn254Code;
+use crate::{
+    bn254_ecmul, bn254_ecmul_output_size, bn254_ecmul_validate_input, bn254_ecpairing,
+    bn254_ecpairing_gas, bn254_ecpairing_output_size, bn254_ecpairing_validate_input, Bn254Result,
+};
+use std::os::raw::{c_int, c_uchar, c_uint};
+
+/// Gas cost of a single EIP-1108 (post-Istanbul) ECMUL call. ECPAIRING's
+/// variable cost is computed by [`bn254_ecpairing_gas`].
+pub const BN254_ECMUL_GAS: u64 = 6_000;
+
+/// The last byte of the well-known 20-byte precompile address for ECMUL.
+pub const ECMUL_PRECOMPILE_ADDRESS: u8 = 0x07;
+/// The last byte of the well-known 20-byte precompile address for
+/// ECPAIRING.
+pub const ECPAIRING_PRECOMPILE_ADDRESS: u8 = 0x08;
+
+/// Subset of `evmc_status_code` (see evmc.h) this dispatcher can produce.
+/// Generic `Failure` (1) is omitted: every failure path here already maps
+/// to a more specific code (`OutOfGas`, `PrecompileFailure`, or
+/// `ArgumentOutOfRange`), so there's no call site that would ever produce
+/// it.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvmcStatusCode {
+    Success = 0,
+    OutOfGas = 3,
+    PrecompileFailure = 12,
+    ArgumentOutOfRange = 14,
+}
+
+/// A fixed-layout, EVMC-`evmc_result`-style call result: status, output
+/// slice, and remaining gas. Unlike the real `evmc_result`, there is no
+/// `release` callback or `create_address` union member — the caller
+/// supplies and owns the output buffer, so there is nothing for this
+/// struct to free.
+#[repr(C)]
+pub struct EvmcResult {
+    pub status_code: c_int,
+    pub output_data: *const c_uchar,
+    pub output_size: usize,
+    pub gas_left: i64,
+}
+
+// The ABI layout must stay small and stable; a generous bound so an honest
+// future addition doesn't fail to compile, while still catching a gross
+// accidental size regression (e.g. an inadvertently embedded buffer).
+const _: () = assert!(std::mem::size_of::<EvmcResult>() <= 64);
+
+impl EvmcResult {
+    fn success(output_data: *const c_uchar, output_size: usize, gas_left: i64) -> Self {
+        Self {
+            status_code: EvmcStatusCode::Success as c_int,
+            output_data,
+            output_size,
+            gas_left,
+        }
+    }
+
+    fn failure(status: EvmcStatusCode) -> Self {
+        Self {
+            status_code: status as c_int,
+            output_data: std::ptr::null(),
+            output_size: 0,
+            gas_left: 0,
+        }
+    }
+}
+
+/// Map a [`Bn254Code`] validation failure onto the closest EVMC status
+/// code: a malformed input shape is the caller's fault
+/// (`ArgumentOutOfRange`), while a bad field element, off-curve point, or
+/// off-subgroup point is the precompile rejecting the call
+/// (`PrecompileFailure`).
+fn bn254_code_to_evmc_status(code: Bn254Code) -> EvmcStatusCode {
+    if code.is_success() {
+        return EvmcStatusCode::Success;
+    }
+    match code.group_id() {
+        Bn254Code::GROUP_INPUT_SHAPE => EvmcStatusCode::ArgumentOutOfRange,
+        _ => EvmcStatusCode::PrecompileFailure,
+    }
+}
+
+/// Build the [`EvmcResult`] for a failed `*_validate_input` call, writing
+/// `code` into the caller's `output` buffer (big-endian, via
+/// [`Bn254Code::write_to_be_bytes`]) when it has room for the two bytes
+/// that takes. The coarse `status_code` alone collapses every failure in a
+/// group down to one of two [`EvmcStatusCode`] variants; a caller that
+/// wants to know exactly which check failed can decode the buffer with
+/// [`Bn254Code::from_be_bytes`] instead.
+fn validation_failure(
+    code: Bn254Code,
+    output: *mut c_uchar,
+    output_capacity: c_uint,
+) -> EvmcResult {
+    let status = bn254_code_to_evmc_status(code);
+    if output.is_null() || output_capacity < 2 {
+        return EvmcResult::failure(status);
+    }
+    let mut bytes = [0u8; 2];
+    code.write_to_be_bytes(&mut bytes);
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), output, 2);
+    }
+    EvmcResult {
+        status_code: status as c_int,
+        output_data: output,
+        output_size: 2,
+        gas_left: 0,
+    }
+}
+
+/// Route a BN254 precompile call (by the last byte of its well-known
+/// address) to `bn254_ecmul` or `bn254_ecpairing`, pre-validating the
+/// input and pre-sizing the output the same way a real EVMC host would:
+/// check the input and the EIP-1108 gas cost of the EIP-196/EIP-197
+/// precompile, and only then run the computation into `output`. ECMUL
+/// validates before the gas check; ECPAIRING checks gas first (see the
+/// comment in that branch).
+///
+/// `output` must be at least as large as the callee's `*_output_size()`;
+/// callers can call `bn254_ecmul_output_size`/`bn254_ecpairing_output_size`
+/// directly to size it. On success, `EvmcResult::output_data` points into
+/// the caller's own `output` buffer (never a dispatcher-owned allocation).
+/// On a validation failure, if `output` has room for 2 bytes, those bytes
+/// are the failing [`Bn254Code`] (see [`validation_failure`]) instead of
+/// precompile output.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes, and `output` for
+/// writes of `output_capacity` bytes, when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bn254_evmc_execute(
+    precompile_address: c_uchar,
+    input: *const c_uchar,
+    input_len: c_uint,
+    gas_limit: i64,
+    output: *mut c_uchar,
+    output_capacity: c_uint,
+) -> EvmcResult {
+    match precompile_address {
+        ECMUL_PRECOMPILE_ADDRESS => {
+            let validate_code =
+                Bn254Code::from(unsafe { bn254_ecmul_validate_input(input, input_len) } as u16);
+            if !validate_code.is_success() {
+                return validation_failure(validate_code, output, output_capacity);
+            }
+
+            let gas_cost = BN254_ECMUL_GAS as i64;
+            if gas_limit < gas_cost {
+                return EvmcResult::failure(EvmcStatusCode::OutOfGas);
+            }
+
+            let required = bn254_ecmul_output_size();
+            if output_capacity < required {
+                return EvmcResult::failure(EvmcStatusCode::ArgumentOutOfRange);
+            }
+
+            let status = unsafe { bn254_ecmul(input, input_len, output, output_capacity) };
+            if status != Bn254Result::Success as c_int {
+                return EvmcResult::failure(EvmcStatusCode::PrecompileFailure);
+            }
+
+            EvmcResult::success(output, required as usize, gas_limit - gas_cost)
+        }
+        ECPAIRING_PRECOMPILE_ADDRESS => {
+            // Check gas first: `bn254_ecpairing_gas` is O(1) from `input_len`
+            // alone, while `bn254_ecpairing_validate_input` is O(num_pairs)
+            // field/curve/subgroup validation. Validating first would let a
+            // caller force a full validation pass over a huge input before
+            // being rejected for a gas limit that was never going to cover
+            // it.
+            let gas_cost = bn254_ecpairing_gas(input_len) as i64;
+            if gas_limit < gas_cost {
+                return EvmcResult::failure(EvmcStatusCode::OutOfGas);
+            }
+
+            let validate_code = Bn254Code::from(
+                unsafe { bn254_ecpairing_validate_input(input, input_len) } as u16,
+            );
+            if !validate_code.is_success() {
+                return validation_failure(validate_code, output, output_capacity);
+            }
+
+            let required = bn254_ecpairing_output_size();
+            if output_capacity < required {
+                return EvmcResult::failure(EvmcStatusCode::ArgumentOutOfRange);
+            }
+
+            let status = unsafe { bn254_ecpairing(input, input_len, output, output_capacity) };
+            if status != Bn254Result::Success as c_int {
+                return EvmcResult::failure(EvmcStatusCode::PrecompileFailure);
+            }
+
+            EvmcResult::success(output, required as usize, gas_limit - gas_cost)
+        }
+        _ => EvmcResult::failure(EvmcStatusCode::ArgumentOutOfRange),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evmc_result_layout() {
+        assert_eq!(std::mem::align_of::<EvmcResult>(), std::mem::align_of::<*const u8>());
+        assert_eq!(std::mem::size_of::<EvmcResult>(), 32);
+    }
+
+    #[test]
+    fn test_evmc_execute_rejects_unknown_address() {
+        let result = unsafe {
+            bn254_evmc_execute(0x09, std::ptr::null(), 0, 100_000, std::ptr::null_mut(), 0)
+        };
+        assert_eq!(
+            result.status_code,
+            EvmcStatusCode::ArgumentOutOfRange as c_int
+        );
+        assert!(result.output_data.is_null());
+        assert_eq!(result.gas_left, 0);
+    }
+
+    #[test]
+    fn test_evmc_execute_ecmul_out_of_gas() {
+        let input = [0u8; 96];
+        let mut output = [0u8; 64];
+        let result = unsafe {
+            bn254_evmc_execute(
+                ECMUL_PRECOMPILE_ADDRESS,
+                input.as_ptr(),
+                96,
+                (BN254_ECMUL_GAS - 1) as i64,
+                output.as_mut_ptr(),
+                64,
+            )
+        };
+        assert_eq!(result.status_code, EvmcStatusCode::OutOfGas as c_int);
+        assert_eq!(result.gas_left, 0);
+    }
+
+    #[test]
+    fn test_evmc_execute_ecmul_success() {
+        // (0, 0) * 0 is the point at infinity, a trivially valid input.
+        let input = [0u8; 96];
+        let mut output = [0xffu8; 64];
+        let result = unsafe {
+            bn254_evmc_execute(
+                ECMUL_PRECOMPILE_ADDRESS,
+                input.as_ptr(),
+                96,
+                1_000_000,
+                output.as_mut_ptr(),
+                64,
+            )
+        };
+        assert_eq!(result.status_code, EvmcStatusCode::Success as c_int);
+        assert_eq!(result.gas_left, 1_000_000 - BN254_ECMUL_GAS as i64);
+        assert_eq!(result.output_size, 64);
+        assert_eq!(output, [0u8; 64]);
+    }
+
+    #[test]
+    fn test_evmc_execute_ecmul_validation_failure_writes_code_to_output() {
+        // Too-short input: `bn254_ecmul_validate_input` rejects it before
+        // the dispatcher ever gets to the gas/output-size checks.
+        let input = [0u8; 64];
+        let mut output = [0xffu8; 64];
+        let result = unsafe {
+            bn254_evmc_execute(
+                ECMUL_PRECOMPILE_ADDRESS,
+                input.as_ptr(),
+                64,
+                1_000_000,
+                output.as_mut_ptr(),
+                64,
+            )
+        };
+        assert_eq!(
+            result.status_code,
+            EvmcStatusCode::ArgumentOutOfRange as c_int
+        );
+        assert_eq!(result.output_size, 2);
+        assert_eq!(result.output_data, output.as_ptr());
+        assert_eq!(
+            Bn254Code::from_be_bytes([output[0], output[1]]),
+            Bn254Code::ECMUL_INPUT_TOO_SHORT
+        );
+    }
+
+    #[test]
+    fn test_evmc_execute_ecpairing_empty_input_success() {
+        // A non-null, zero-length input: EIP-197 defines the empty
+        // ECPAIRING input as trivially true.
+        let input: [u8; 0] = [];
+        let mut output = [0xffu8; 32];
+        let result = unsafe {
+            bn254_evmc_execute(
+                ECPAIRING_PRECOMPILE_ADDRESS,
+                input.as_ptr(),
+                0,
+                1_000_000,
+                output.as_mut_ptr(),
+                32,
+            )
+        };
+        assert_eq!(result.status_code, EvmcStatusCode::Success as c_int);
+        assert_eq!(result.output_size, 32);
+    }
+}
@@ -1,7 +1,15 @@
+mod bigint;
+mod bls12_381_code;
+mod bn254_code;
+mod evmc;
+
 use ark_bn254::{Bn254, G1Affine, G2Affine};
 use ark_bls12_381::{Bls12_381, G1Affine as BlsG1Affine, G2Affine as BlsG2Affine};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::{BigInteger, One, PrimeField, Zero};
+use bigint::BigUint;
+use bls12_381_code::Bls12381Code;
+use bn254_code::Bn254Code;
 /// BN254 Wrapper Library for Zig Integration
 ///
 /// This library provides C-compatible bindings for BN254 elliptic curve operations
@@ -134,6 +142,135 @@ pub unsafe extern "C" fn bn254_ecmul(
     Bn254Result::Success as c_int
 }
 
+/// Perform elliptic curve point addition (ECADD)
+///
+/// Input format (128 bytes):
+/// - Bytes 0-31: first point x coordinate (big-endian)
+/// - Bytes 32-63: first point y coordinate (big-endian)
+/// - Bytes 64-95: second point x coordinate (big-endian)
+/// - Bytes 96-127: second point y coordinate (big-endian)
+///
+/// Output format (64 bytes):
+/// - Bytes 0-31: result x coordinate (big-endian)
+/// - Bytes 32-63: result y coordinate (big-endian)
+///
+/// Returns Bn254Result::Success on success, error code otherwise
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+/// - Pointers must not be null when lengths are non-zero
+/// - Caller must ensure input/output buffers don't overlap
+#[no_mangle]
+pub unsafe extern "C" fn bn254_ecadd(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    // Validate input parameters
+    if input.is_null() || output.is_null() {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    if output_len < 64 {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    // Convert pointers to slices
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    // Ensure we have exactly 128 bytes of input, zero-padded like the other functions
+    let mut padded_input = [0u8; 128];
+    let copy_len = std::cmp::min(input_slice.len(), 128);
+    padded_input[..copy_len].copy_from_slice(&input_slice[..copy_len]);
+
+    // Parse the two G1 point coordinates (64 bytes each)
+    let p1_x_bytes = &padded_input[0..32];
+    let p1_y_bytes = &padded_input[32..64];
+    let p2_x_bytes = &padded_input[64..96];
+    let p2_y_bytes = &padded_input[96..128];
+
+    use ark_bn254::Fq;
+
+    let p1_x = Fq::from_be_bytes_mod_order(p1_x_bytes);
+    let p1_y = Fq::from_be_bytes_mod_order(p1_y_bytes);
+    let p2_x = Fq::from_be_bytes_mod_order(p2_x_bytes);
+    let p2_y = Fq::from_be_bytes_mod_order(p2_y_bytes);
+
+    // Check for point at infinity (both coordinates zero) before on-curve checks,
+    // matching the convention used by bn254_ecmul/bn254_ecpairing
+    let p1 = if p1_x.is_zero() && p1_y.is_zero() {
+        G1Affine::zero()
+    } else {
+        match G1Affine::new_unchecked(p1_x, p1_y) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..64].fill(0);
+                return Bn254Result::Success as c_int;
+            }
+        }
+    };
+
+    let p2 = if p2_x.is_zero() && p2_y.is_zero() {
+        G1Affine::zero()
+    } else {
+        match G1Affine::new_unchecked(p2_x, p2_y) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..64].fill(0);
+                return Bn254Result::Success as c_int;
+            }
+        }
+    };
+
+    // Perform addition
+    let result = (p1 + p2).into_affine();
+
+    // Handle point at infinity
+    if result.is_zero() {
+        output_slice[..64].fill(0);
+        return Bn254Result::Success as c_int;
+    }
+
+    // Convert result to bytes (big-endian)
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    let x_bytes = x_result.into_bigint().to_bytes_be();
+    let y_bytes = y_result.into_bigint().to_bytes_be();
+
+    // Pad to 32 bytes and copy to output
+    output_slice[..64].fill(0);
+    output_slice[32 - x_bytes.len()..32].copy_from_slice(&x_bytes);
+    output_slice[32 + (32 - y_bytes.len())..64].copy_from_slice(&y_bytes);
+
+    Bn254Result::Success as c_int
+}
+
+/// Get the expected output size for ECADD
+#[no_mangle]
+pub extern "C" fn bn254_ecadd_output_size() -> c_uint {
+    64
+}
+
+/// Validate ECADD input format
+#[no_mangle]
+pub extern "C" fn bn254_ecadd_validate_input(input: *const c_uchar, input_len: c_uint) -> c_int {
+    if input.is_null() {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    if input_len < 128 {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    Bn254Result::Success as c_int
+}
+
 /// Perform elliptic curve pairing check (ECPAIRING)
 ///
 /// Input format (multiple of 192 bytes):
@@ -273,457 +410,2493 @@ pub extern "C" fn bn254_ecmul_output_size() -> c_uint {
     64
 }
 
-/// Get the expected output size for ECPAIRING  
+/// Get the expected output size for ECPAIRING
 #[no_mangle]
 pub extern "C" fn bn254_ecpairing_output_size() -> c_uint {
     32
 }
 
-/// Validate ECMUL input format
+/// Base gas cost of EIP-197/EIP-1108 ECPAIRING, independent of pair count.
+pub const BN254_ECPAIRING_BASE_GAS: u64 = 45_000;
+/// Per-pair gas cost of EIP-197/EIP-1108 ECPAIRING.
+pub const BN254_ECPAIRING_PER_PAIR_GAS: u64 = 34_000;
+
+/// Compute the EIP-1108 gas cost of an ECPAIRING call given its input
+/// length, so callers can reject out-of-gas inputs before invoking
+/// [`bn254_ecpairing`].
 #[no_mangle]
-pub extern "C" fn bn254_ecmul_validate_input(input: *const c_uchar, input_len: c_uint) -> c_int {
-    if input.is_null() {
-        return Bn254Result::InvalidInput as c_int;
-    }
+pub extern "C" fn bn254_ecpairing_gas(input_len: c_uint) -> c_uint {
+    let num_pairs = (input_len as u64) / 192;
+    (BN254_ECPAIRING_BASE_GAS + BN254_ECPAIRING_PER_PAIR_GAS * num_pairs) as c_uint
+}
 
-    if input_len < 96 {
-        return Bn254Result::InvalidInput as c_int;
+/// Window width (in bits) Pippenger's bucket method should use for an
+/// `n`-term multi-scalar multiplication: wider windows trade more bucket
+/// memory for fewer passes, and the break-even point scales with `ln(n)`.
+fn bn254_msm_window_bits(n: usize) -> usize {
+    if n < 32 {
+        3
+    } else {
+        ((n as f64).ln().ceil() as usize).max(1)
     }
-
-    Bn254Result::Success as c_int
 }
 
-/// Validate ECPAIRING input format
-#[no_mangle]
-pub extern "C" fn bn254_ecpairing_validate_input(
-    input: *const c_uchar,
-    input_len: c_uint,
-) -> c_int {
-    if input.is_null() {
-        return Bn254Result::InvalidInput as c_int;
+/// Extract the `c`-bit digit starting at bit `window_index * c` (counting
+/// from the least-significant bit) of a 32-byte big-endian scalar.
+fn bn254_msm_window_digit(scalar_bytes: &[u8], window_index: usize, c: usize) -> usize {
+    debug_assert_eq!(scalar_bytes.len(), 32);
+    let mut digit = 0usize;
+    for bit_offset in 0..c {
+        let bit_index = window_index * c + bit_offset;
+        if bit_index >= 256 {
+            break;
+        }
+        let byte_index = 31 - bit_index / 8;
+        let bit_in_byte = bit_index % 8;
+        let bit = (scalar_bytes[byte_index] >> bit_in_byte) & 1;
+        digit |= (bit as usize) << bit_offset;
     }
+    digit
+}
 
-    if input_len % 192 != 0 {
-        return Bn254Result::InvalidInput as c_int;
+/// Sum `N` scalar·point products via Pippenger's bucket method.
+///
+/// `points` and `scalars` must have the same length. Partitions each
+/// 256-bit scalar into `c`-bit windows (`c` chosen by
+/// [`bn254_msm_window_bits`]); within each window, every point is added
+/// into the bucket indexed by its window digit, each window is then
+/// reduced to a single sum via the running-suffix-sum trick (an
+/// accumulating suffix sum of `i * bucket[i]`, computed by folding the
+/// buckets from the top down), and the per-window sums are combined
+/// high-to-low with `c` doublings between consecutive windows.
+fn bn254_msm_pippenger(points: &[G1Affine], scalars: &[&[u8]]) -> ark_bn254::G1Projective {
+    debug_assert_eq!(points.len(), scalars.len());
+
+    if points.is_empty() {
+        return ark_bn254::G1Projective::zero();
     }
 
-    Bn254Result::Success as c_int
-}
+    let c = bn254_msm_window_bits(points.len());
+    let num_buckets = (1usize << c) - 1;
+    let num_windows = 256usize.div_ceil(c);
+
+    let mut window_sums = Vec::with_capacity(num_windows);
+    for w in 0..num_windows {
+        let mut buckets = vec![ark_bn254::G1Projective::zero(); num_buckets];
+        for (point, scalar_bytes) in points.iter().zip(scalars.iter()) {
+            let digit = bn254_msm_window_digit(scalar_bytes, w, c);
+            if digit != 0 {
+                buckets[digit - 1] += *point;
+            }
+        }
 
-/// Result codes for BLS12-381 operations
-#[repr(C)]
-pub enum Bls12381Result {
-    Success = 0,
-    InvalidInput = 1,
-    InvalidPoint = 2,
-    InvalidScalar = 3,
-    ComputationFailed = 4,
+        // Running-suffix-sum trick: after processing bucket `i` (from the
+        // top down), `running_sum` holds `sum_{j=i}^{num_buckets} bucket[j]`
+        // and `window_sum` accumulates `sum_i i * bucket[i]`.
+        let mut running_sum = ark_bn254::G1Projective::zero();
+        let mut window_sum = ark_bn254::G1Projective::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            window_sum += running_sum;
+        }
+        window_sums.push(window_sum);
+    }
+
+    let mut result = ark_bn254::G1Projective::zero();
+    for (w, window_sum) in window_sums.into_iter().enumerate().rev() {
+        if w != num_windows - 1 {
+            for _ in 0..c {
+                result += result;
+            }
+        }
+        result += window_sum;
+    }
+    result
 }
 
-/// Perform BLS12-381 G1 addition
+/// Perform an EIP-196 ECMUL-equivalent multi-scalar multiplication (MSM)
+/// over `N` packed `(point, scalar)` triples in one call, via Pippenger's
+/// bucket method (see [`bn254_msm_pippenger`]) instead of the
+/// quadratic-ish cost of looping single-shot [`bn254_ecmul`] calls.
 ///
-/// Input format (128 bytes):
-/// - Bytes 0-47: first point x coordinate (big-endian)
-/// - Bytes 48-95: first point y coordinate (big-endian)
-/// - Bytes 96-143: second point x coordinate (big-endian)
-/// - Bytes 144-191: second point y coordinate (big-endian)
+/// Input format (96 * N bytes): `N` back-to-back triples, each laid out
+/// exactly like [`bn254_ecmul`]'s input (x, y, scalar, 32 bytes each).
 ///
-/// Output format (96 bytes):
-/// - Bytes 0-47: result x coordinate (big-endian)
-/// - Bytes 48-95: result y coordinate (big-endian)
+/// Output format (64 bytes): the summed result, x then y (big-endian), or
+/// all zero for the point at infinity.
+///
+/// Matches [`bn254_ecmul`]'s handling of a malformed point: rather than
+/// erroring, the whole call returns the point at infinity.
 ///
 /// # Safety
 ///
-/// This function dereferences raw pointers and requires:
-/// - `input` must be valid for reads of `input_len` bytes
-/// - `output` must be valid for writes of `output_len` bytes
+/// `input` must be valid for reads of `input_len` bytes and `output` for
+/// writes of `output_len` bytes, when non-null.
 #[no_mangle]
-pub unsafe extern "C" fn bls12_381_g1_add(
+pub unsafe extern "C" fn bn254_ecmsm(
     input: *const c_uchar,
     input_len: c_uint,
     output: *mut c_uchar,
     output_len: c_uint,
 ) -> c_int {
     if input.is_null() || output.is_null() {
-        return Bls12381Result::InvalidInput as c_int;
+        return Bn254Result::InvalidInput as c_int;
     }
 
-    if input_len < 256 || output_len < 128 {
-        return Bls12381Result::InvalidInput as c_int;
+    if input_len % 96 != 0 || output_len < 64 {
+        return Bn254Result::InvalidInput as c_int;
     }
 
     let input_slice = std::slice::from_raw_parts(input, input_len as usize);
     let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
 
-    // Parse first G1 point (128 bytes)
-    let p1_x_bytes = &input_slice[0..48];
-    let p1_y_bytes = &input_slice[48..96];
-    
-    // Parse second G1 point (128 bytes)
-    let p2_x_bytes = &input_slice[128..176];
-    let p2_y_bytes = &input_slice[176..224];
+    use ark_bn254::Fq;
 
-    use ark_bls12_381::Fq;
-    
-    let p1_x = Fq::from_be_bytes_mod_order(p1_x_bytes);
-    let p1_y = Fq::from_be_bytes_mod_order(p1_y_bytes);
-    let p2_x = Fq::from_be_bytes_mod_order(p2_x_bytes);
-    let p2_y = Fq::from_be_bytes_mod_order(p2_y_bytes);
+    let num_terms = (input_len as usize) / 96;
+    let mut points = Vec::with_capacity(num_terms);
+    let mut scalars = Vec::with_capacity(num_terms);
 
-    // Check for point at infinity
-    let p1 = if p1_x.is_zero() && p1_y.is_zero() {
-        BlsG1Affine::zero()
-    } else {
-        match BlsG1Affine::new_unchecked(p1_x, p1_y) {
-            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
-            _ => {
-                output_slice[..128].fill(0);
-                return Bls12381Result::Success as c_int;
-            }
-        }
-    };
+    for i in 0..num_terms {
+        let offset = i * 96;
+        let x_bytes = &input_slice[offset..offset + 32];
+        let y_bytes = &input_slice[offset + 32..offset + 64];
+        let scalar_bytes = &input_slice[offset + 64..offset + 96];
 
-    let p2 = if p2_x.is_zero() && p2_y.is_zero() {
-        BlsG1Affine::zero()
-    } else {
-        match BlsG1Affine::new_unchecked(p2_x, p2_y) {
-            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
-            _ => {
-                output_slice[..128].fill(0);
-                return Bls12381Result::Success as c_int;
+        let x = Fq::from_be_bytes_mod_order(x_bytes);
+        let y = Fq::from_be_bytes_mod_order(y_bytes);
+
+        let point = if x.is_zero() && y.is_zero() {
+            G1Affine::zero()
+        } else {
+            match G1Affine::new_unchecked(x, y) {
+                p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+                _ => {
+                    output_slice[..64].fill(0);
+                    return Bn254Result::Success as c_int;
+                }
             }
-        }
-    };
+        };
 
-    // Perform addition
-    let result = (p1 + p2).into_affine();
+        // Kept as raw bytes rather than parsed into `Fr`: Pippenger's window
+        // digits are read directly off the big-endian encoding.
+        points.push(point);
+        scalars.push(scalar_bytes);
+    }
+
+    let result = bn254_msm_pippenger(&points, &scalars).into_affine();
 
-    // Handle point at infinity
     if result.is_zero() {
-        output_slice[..128].fill(0);
-        return Bls12381Result::Success as c_int;
+        output_slice[..64].fill(0);
+        return Bn254Result::Success as c_int;
     }
 
-    // Convert result to bytes
-    let x_result = result.x().expect("x coordinate should exist");
-    let y_result = result.y().expect("y coordinate should exist");
+    let x_bytes = result
+        .x()
+        .expect("x coordinate should exist")
+        .into_bigint()
+        .to_bytes_be();
+    let y_bytes = result
+        .y()
+        .expect("y coordinate should exist")
+        .into_bigint()
+        .to_bytes_be();
 
-    let x_bytes = x_result.into_bigint().to_bytes_be();
-    let y_bytes = y_result.into_bigint().to_bytes_be();
+    output_slice[..64].fill(0);
+    output_slice[32 - x_bytes.len()..32].copy_from_slice(&x_bytes);
+    output_slice[64 - y_bytes.len()..64].copy_from_slice(&y_bytes);
 
-    // Pad and copy to output
-    output_slice[..128].fill(0);
-    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
-    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+    Bn254Result::Success as c_int
+}
 
-    Bls12381Result::Success as c_int
+/// Output size for `bn254_ecmsm` (64 bytes, matching [`bn254_ecmul`]).
+#[no_mangle]
+pub extern "C" fn bn254_ecmsm_output_size() -> c_uint {
+    64
 }
 
-/// Perform BLS12-381 G1 scalar multiplication
-///
-/// Input format (144 bytes):
-/// - Bytes 0-47: x coordinate (big-endian)
-/// - Bytes 48-95: y coordinate (big-endian)
-/// - Bytes 96-127: scalar (big-endian)
-///
-/// Output format (128 bytes):
-/// - Bytes 0-47: result x coordinate (big-endian)
-/// - Bytes 48-95: result y coordinate (big-endian)
+/// Validate a `bn254_ecmsm` input: length must be a multiple of 96 bytes
+/// (one `(point, scalar)` triple each), and every embedded point must pass
+/// the same field-range/on-curve checks as
+/// [`bn254_ecmul_validate_input`].
 ///
 /// # Safety
 ///
-/// This function dereferences raw pointers and requires:
-/// - `input` must be valid for reads of `input_len` bytes
-/// - `output` must be valid for writes of `output_len` bytes
+/// `input` must be valid for reads of `input_len` bytes when non-null.
 #[no_mangle]
-pub unsafe extern "C" fn bls12_381_g1_mul(
+pub unsafe extern "C" fn bn254_ecmsm_validate_input(
     input: *const c_uchar,
     input_len: c_uint,
-    output: *mut c_uchar,
-    output_len: c_uint,
 ) -> c_int {
-    if input.is_null() || output.is_null() {
-        return Bls12381Result::InvalidInput as c_int;
+    if input.is_null() {
+        return Bn254Code::NULL_POINTER.raw() as c_int;
     }
 
-    if input_len < 160 || output_len < 128 {
-        return Bls12381Result::InvalidInput as c_int;
+    if input_len % 96 != 0 {
+        return Bn254Code::ECMUL_INPUT_TOO_SHORT.raw() as c_int;
     }
 
     let input_slice = std::slice::from_raw_parts(input, input_len as usize);
-    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+    let num_terms = (input_len as usize) / 96;
+
+    for i in 0..num_terms {
+        let offset = i * 96;
+        let code =
+            validate_bn254_g1_point(&input_slice[offset..offset + 32], &input_slice[offset + 32..offset + 64]);
+        if !code.is_success() {
+            return code.raw() as c_int;
+        }
+    }
 
-    // Parse G1 point (128 bytes)
-    let x_bytes = &input_slice[0..48];
-    let y_bytes = &input_slice[48..96];
-    
-    // Parse scalar (32 bytes)
-    let scalar_bytes = &input_slice[128..160];
+    Bn254Code::SUCCESS.raw() as c_int
+}
 
-    use ark_bls12_381::{Fq, Fr};
-    
-    let x_coord = Fq::from_be_bytes_mod_order(x_bytes);
-    let y_coord = Fq::from_be_bytes_mod_order(y_bytes);
-    let scalar = Fr::from_be_bytes_mod_order(scalar_bytes);
+/// Strictly decode a 32-byte big-endian BN254 `Fq` coordinate, rejecting
+/// values that are `>= p` instead of silently reducing them the way
+/// `Fq::from_be_bytes_mod_order` does (checked by re-encoding and comparing
+/// against the original bytes).
+fn decode_bn254_fq_strict(bytes: &[u8]) -> Option<ark_bn254::Fq> {
+    let value = ark_bn254::Fq::from_be_bytes_mod_order(bytes);
+    let reencoded_be = value.into_bigint().to_bytes_be();
+    let mut reencoded = [0u8; 32];
+    reencoded[32 - reencoded_be.len()..].copy_from_slice(&reencoded_be);
+    if reencoded != *bytes {
+        return None;
+    }
+    Some(value)
+}
 
-    // Check for point at infinity
-    let point = if x_coord.is_zero() && y_coord.is_zero() {
-        BlsG1Affine::zero()
-    } else {
-        match BlsG1Affine::new_unchecked(x_coord, y_coord) {
-            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
-            _ => {
-                output_slice[..128].fill(0);
-                return Bls12381Result::Success as c_int;
-            }
-        }
+/// Validate a single BN254 G1 point's 64-byte (x, y) encoding: field-range
+/// for each coordinate, then curve membership (treating `(0, 0)` as the
+/// canonical point at infinity).
+fn validate_bn254_g1_point(x_bytes: &[u8], y_bytes: &[u8]) -> Bn254Code {
+    let Some(x) = decode_bn254_fq_strict(x_bytes) else {
+        return Bn254Code::G1_X_OUT_OF_RANGE;
+    };
+    let Some(y) = decode_bn254_fq_strict(y_bytes) else {
+        return Bn254Code::G1_Y_OUT_OF_RANGE;
     };
 
-    // Perform scalar multiplication
-    let result = (point * scalar).into_affine();
+    if x.is_zero() && y.is_zero() {
+        return Bn254Code::SUCCESS;
+    }
 
-    // Handle point at infinity
-    if result.is_zero() {
-        output_slice[..128].fill(0);
-        return Bls12381Result::Success as c_int;
+    if !G1Affine::new_unchecked(x, y).is_on_curve() {
+        return Bn254Code::G1_NOT_ON_CURVE;
     }
 
-    // Convert result to bytes
-    let x_result = result.x().expect("x coordinate should exist");
-    let y_result = result.y().expect("y coordinate should exist");
+    Bn254Code::SUCCESS
+}
 
-    let x_bytes = x_result.into_bigint().to_bytes_be();
-    let y_bytes = y_result.into_bigint().to_bytes_be();
+/// Validate a single BN254 G2 point's 128-byte (x, y) Fp2 encoding: field
+/// range for each of the four components, on-curve, and subgroup
+/// membership (a curve point may lie outside the r-order subgroup and
+/// silently corrupt a pairing, so this is checked explicitly rather than
+/// only verifying `is_on_curve`).
+fn validate_bn254_g2_point(
+    x_c0_bytes: &[u8],
+    x_c1_bytes: &[u8],
+    y_c0_bytes: &[u8],
+    y_c1_bytes: &[u8],
+) -> Bn254Code {
+    let (Some(x_c0), Some(x_c1), Some(y_c0), Some(y_c1)) = (
+        decode_bn254_fq_strict(x_c0_bytes),
+        decode_bn254_fq_strict(x_c1_bytes),
+        decode_bn254_fq_strict(y_c0_bytes),
+        decode_bn254_fq_strict(y_c1_bytes),
+    ) else {
+        return Bn254Code::G2_COORDINATE_OUT_OF_RANGE;
+    };
 
-    // Pad and copy to output
-    output_slice[..128].fill(0);
-    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
-    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+    use ark_bn254::Fq2;
+    let x = Fq2::new(x_c0, x_c1);
+    let y = Fq2::new(y_c0, y_c1);
 
-    Bls12381Result::Success as c_int
+    if x.is_zero() && y.is_zero() {
+        return Bn254Code::SUCCESS;
+    }
+
+    let point = G2Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        return Bn254Code::G2_NOT_ON_CURVE;
+    }
+
+    // Membership in the prime-order subgroup, not merely on-curve: BN254's
+    // G2 curve has points outside the r-order subgroup, and arkworks'
+    // `is_in_correct_subgroup_assuming_on_curve` implements the
+    // scalar-multiplication-by-cofactor check for exactly this reason.
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Bn254Code::G2_NOT_IN_SUBGROUP;
+    }
+
+    Bn254Code::SUCCESS
 }
 
-/// Perform BLS12-381 G1 multi-scalar multiplication
+/// Validate ECMUL input format
 ///
-/// Input format (variable, 160 * k bytes for k points):
-/// Each 160-byte group contains:
-/// - Bytes 0-47: x coordinate (big-endian)
-/// - Bytes 48-95: y coordinate (big-endian)
-/// - Bytes 96-127: scalar (big-endian)
-/// - Bytes 128-159: padding (ignored)
+/// Beyond null/length checks, this validates the G1 point itself: each
+/// coordinate must be `< p` (the BN254 base field modulus) and the point
+/// (other than `(0, 0)`, the point at infinity) must satisfy the curve
+/// equation.
 ///
-/// Output format (128 bytes):
-/// - Bytes 0-47: result x coordinate (big-endian)
-/// - Bytes 48-95: result y coordinate (big-endian)
+/// Returns a [`Bn254Code`] (see `bn254_code`) packed into a `c_int` via
+/// `code.raw() as c_int`, so FFI consumers can branch on `group_id()`
+/// without string parsing.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bn254_ecmul_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bn254Code::NULL_POINTER.raw() as c_int;
+    }
+
+    if input_len < 96 {
+        return Bn254Code::ECMUL_INPUT_TOO_SHORT.raw() as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let code = validate_bn254_g1_point(&input_slice[0..32], &input_slice[32..64]);
+
+    code.raw() as c_int
+}
+
+/// Validate ECPAIRING input format
+///
+/// Beyond the overall length check, this validates every G1/G2 point in
+/// each 192-byte chunk: field-range per coordinate, on-curve membership,
+/// and (for G2) membership in the correct prime-order subgroup rather than
+/// merely being on the curve.
+///
+/// Returns a [`Bn254Code`] (see `bn254_code`) packed into a `c_int` via
+/// `code.raw() as c_int`, so FFI consumers can branch on `group_id()`
+/// without string parsing.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bn254_ecpairing_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bn254Code::NULL_POINTER.raw() as c_int;
+    }
+
+    if input_len % 192 != 0 {
+        return Bn254Code::ECPAIRING_LENGTH_NOT_MULTIPLE_OF_192.raw() as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let num_pairs = (input_len as usize) / 192;
+
+    for i in 0..num_pairs {
+        let offset = i * 192;
+
+        let g1_code =
+            validate_bn254_g1_point(&input_slice[offset..offset + 32], &input_slice[offset + 32..offset + 64]);
+        if !g1_code.is_success() {
+            return g1_code.raw() as c_int;
+        }
+
+        let g2_code = validate_bn254_g2_point(
+            &input_slice[offset + 64..offset + 96],
+            &input_slice[offset + 96..offset + 128],
+            &input_slice[offset + 128..offset + 160],
+            &input_slice[offset + 160..offset + 192],
+        );
+        if !g2_code.is_success() {
+            return g2_code.raw() as c_int;
+        }
+    }
+
+    Bn254Code::SUCCESS.raw() as c_int
+}
+
+/// Parse one of MODEXP's three 32-byte big-endian length fields.
+///
+/// Per EIP-198/EIP-2565 these are full 256-bit lengths, not 64-bit ones —
+/// `None` if any of the high 24 bytes are nonzero, since that means the
+/// encoded length is already far larger than any input this process could
+/// hold, let alone compute over; callers must reject rather than silently
+/// truncate down to the low 8 bytes.
+fn parse_modexp_len(bytes: &[u8]) -> Option<usize> {
+    if bytes[..24].iter().any(|&b| b != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(bytes[24..32].try_into().unwrap()) as usize)
+}
+
+/// Perform modular exponentiation (MODEXP, EIP-198 / EIP-2565)
+///
+/// Input format (variable length):
+/// - Bytes 0-31: `base_len` (big-endian length of the base)
+/// - Bytes 32-63: `exp_len` (big-endian length of the exponent)
+/// - Bytes 64-95: `mod_len` (big-endian length of the modulus)
+/// - Next `base_len` bytes: `base`
+/// - Next `exp_len` bytes: `exp`
+/// - Next `mod_len` bytes: `modulus`
+///
+/// Output format: exactly `mod_len` big-endian bytes containing
+/// `base^exp mod modulus`.
+///
+/// Returns Bn254Result::Success on success, error code otherwise. Unlike the
+/// BN254/BLS operations in this file, base/exp/modulus can each be thousands
+/// of bits wide, so this is backed by the variable-width [`BigUint`] rather
+/// than a fixed arkworks field type.
 ///
 /// # Safety
 ///
 /// This function dereferences raw pointers and requires:
 /// - `input` must be valid for reads of `input_len` bytes
 /// - `output` must be valid for writes of `output_len` bytes
+/// - Pointers must not be null when lengths are non-zero
+/// - Caller must ensure input/output buffers don't overlap
 #[no_mangle]
-pub unsafe extern "C" fn bls12_381_g1_multiexp(
+pub unsafe extern "C" fn modexp(
     input: *const c_uchar,
     input_len: c_uint,
     output: *mut c_uchar,
     output_len: c_uint,
 ) -> c_int {
     if input.is_null() || output.is_null() {
-        return Bls12381Result::InvalidInput as c_int;
+        return Bn254Result::InvalidInput as c_int;
     }
 
-    if input_len % 160 != 0 || output_len < 128 {
-        return Bls12381Result::InvalidInput as c_int;
+    if input_len < 96 {
+        return Bn254Result::InvalidInput as c_int;
     }
 
     let input_slice = std::slice::from_raw_parts(input, input_len as usize);
     let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
 
-    use ark_bls12_381::{Fq, Fr, G1Projective};
-    use ark_ec::VariableBaseMSM;
-    
-    let num_pairs = (input_len as usize) / 160;
-    let mut points = Vec::with_capacity(num_pairs);
-    let mut scalars = Vec::with_capacity(num_pairs);
+    let (base_len, exp_len, mod_len) = match (
+        parse_modexp_len(&input_slice[0..32]),
+        parse_modexp_len(&input_slice[32..64]),
+        parse_modexp_len(&input_slice[64..96]),
+    ) {
+        (Some(base_len), Some(exp_len), Some(mod_len)) => (base_len, exp_len, mod_len),
+        _ => return Bn254Result::InvalidInput as c_int,
+    };
 
-    for i in 0..num_pairs {
-        let offset = i * 160;
-        let x_bytes = &input_slice[offset..offset + 48];
-        let y_bytes = &input_slice[offset + 48..offset + 96];
-        let scalar_bytes = &input_slice[offset + 128..offset + 160];
+    // These lengths come straight from attacker-controlled calldata. Past
+    // the end of the actual input they only ever contribute zero padding
+    // (see `read_field` below), so a length longer than the whole input
+    // can't represent anything the caller couldn't already express with a
+    // shorter one — reject it here instead of sizing an allocation off of
+    // it and aborting the process on a capacity overflow.
+    let max_field_len = input_len as usize;
+    if base_len > max_field_len || exp_len > max_field_len || mod_len > max_field_len {
+        return Bn254Result::InvalidInput as c_int;
+    }
 
-        let x_coord = Fq::from_be_bytes_mod_order(x_bytes);
-        let y_coord = Fq::from_be_bytes_mod_order(y_bytes);
-        let scalar = Fr::from_be_bytes_mod_order(scalar_bytes);
+    if mod_len == 0 {
+        return Bn254Result::Success as c_int;
+    }
 
-        // Skip point at infinity
-        if x_coord.is_zero() && y_coord.is_zero() {
-            continue;
+    if output_len < mod_len as c_uint {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    // Bodies are zero-padded past the end of the actual input, matching the
+    // Ethereum spec's treatment of a short MODEXP input.
+    let read_field = |offset: usize, len: usize| -> Vec<u8> {
+        let mut field = vec![0u8; len];
+        let start = 96 + offset;
+        if start < input_slice.len() {
+            let available = std::cmp::min(len, input_slice.len() - start);
+            field[..available].copy_from_slice(&input_slice[start..start + available]);
         }
+        field
+    };
 
-        let point = match BlsG1Affine::new_unchecked(x_coord, y_coord) {
-            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
-            _ => continue,
-        };
+    let base_bytes = read_field(0, base_len);
+    let exp_bytes = read_field(base_len, exp_len);
+    let modulus_bytes = read_field(base_len + exp_len, mod_len);
 
-        points.push(point);
-        scalars.push(scalar);
+    let modulus = BigUint::from_be_bytes(&modulus_bytes);
+    if modulus.is_zero() || modulus == BigUint::one() {
+        output_slice[..mod_len].fill(0);
+        return Bn254Result::Success as c_int;
     }
 
-    // Perform multi-scalar multiplication
-    let result = if points.is_empty() {
-        BlsG1Affine::zero()
-    } else {
-        G1Projective::msm(&points, &scalars).unwrap().into_affine()
-    };
+    let base = BigUint::from_be_bytes(&base_bytes);
+    let exp = BigUint::from_be_bytes(&exp_bytes);
 
-    // Handle point at infinity
-    if result.is_zero() {
-        output_slice[..128].fill(0);
-        return Bls12381Result::Success as c_int;
-    }
+    let result = BigUint::modpow(&base, &exp, &modulus);
+    output_slice[..mod_len].copy_from_slice(&result.to_be_bytes(mod_len));
 
-    // Convert result to bytes
-    let x_result = result.x().expect("x coordinate should exist");
-    let y_result = result.y().expect("y coordinate should exist");
+    Bn254Result::Success as c_int
+}
 
-    let x_bytes = x_result.into_bigint().to_bytes_be();
-    let y_bytes = y_result.into_bigint().to_bytes_be();
+/// Get the expected output size for MODEXP, given the 96-byte length header.
+///
+/// Returns 0 if `input_len` is too short to contain the header.
+#[no_mangle]
+pub unsafe extern "C" fn modexp_output_size(input: *const c_uchar, input_len: c_uint) -> c_uint {
+    if input.is_null() || input_len < 96 {
+        return 0;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    u32::from_be_bytes(input_slice[92..96].try_into().unwrap())
+}
 
-    // Pad and copy to output
-    output_slice[..128].fill(0);
-    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
-    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+/// Validate MODEXP input format
+#[no_mangle]
+pub extern "C" fn modexp_validate_input(input: *const c_uchar, input_len: c_uint) -> c_int {
+    if input.is_null() {
+        return Bn254Result::InvalidInput as c_int;
+    }
 
-    Bls12381Result::Success as c_int
+    if input_len < 96 {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    Bn254Result::Success as c_int
 }
 
-/// Perform BLS12-381 pairing check
+/// Recover a signer address (ECRECOVER, address 0x01)
 ///
-/// Input format (variable, 384 * k bytes for k pairs):
-/// Each 384-byte group contains:
-/// - Bytes 0-127: G1 point (x, y coordinates, 48 bytes each + 32 bytes padding)
-/// - Bytes 128-383: G2 point (x and y in Fp2, 96 bytes each + 64 bytes padding)
+/// Input format (128 bytes):
+/// - Bytes 0-31: message hash
+/// - Bytes 32-63: `v` (big-endian, expected to be 27 or 28)
+/// - Bytes 64-95: `r` (big-endian)
+/// - Bytes 96-127: `s` (big-endian)
 ///
-/// Output format (32 bytes):
-/// - 32-byte boolean result (0x00...00 for false, 0x00...01 for true)
+/// Output format (32 bytes): the recovered address, right-aligned (12
+/// leading zero bytes followed by the 20-byte address), or all-zero on any
+/// validation failure (`v` not in `{27, 28}`, `r`/`s` out of range, or no
+/// valid recovery) rather than an error code, matching the
+/// `Bn254Result`/`Bls12381Result` convention already used in this file.
 ///
 /// # Safety
 ///
 /// This function dereferences raw pointers and requires:
 /// - `input` must be valid for reads of `input_len` bytes
 /// - `output` must be valid for writes of `output_len` bytes
+/// - Pointers must not be null when lengths are non-zero
+/// - Caller must ensure input/output buffers don't overlap
 #[no_mangle]
-pub unsafe extern "C" fn bls12_381_pairing(
+pub unsafe extern "C" fn ecrecover(
     input: *const c_uchar,
     input_len: c_uint,
     output: *mut c_uchar,
     output_len: c_uint,
 ) -> c_int {
     if input.is_null() || output.is_null() {
-        return Bls12381Result::InvalidInput as c_int;
+        return Bn254Result::InvalidInput as c_int;
     }
 
-    if input_len % 384 != 0 || output_len < 32 {
-        return Bls12381Result::InvalidInput as c_int;
+    if output_len < 32 {
+        return Bn254Result::InvalidInput as c_int;
     }
 
     let input_slice = std::slice::from_raw_parts(input, input_len as usize);
     let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
 
-    use ark_bls12_381::{Fq, Fq2, Fq12};
-    
-    let num_pairs = (input_len as usize) / 384;
-    let mut g1_points = Vec::with_capacity(num_pairs);
-    let mut g2_points = Vec::with_capacity(num_pairs);
+    let mut padded_input = [0u8; 128];
+    let copy_len = std::cmp::min(input_slice.len(), 128);
+    padded_input[..copy_len].copy_from_slice(&input_slice[..copy_len]);
 
-    // Handle empty input (should return true according to EIP-2537)
-    if input_len == 0 {
+    let hash = &padded_input[0..32];
+    let v_bytes = &padded_input[32..64];
+    let r_bytes = &padded_input[64..96];
+    let s_bytes = &padded_input[96..128];
+
+    // v must be the big-endian encoding of exactly 27 or 28, with all
+    // higher bytes zero.
+    if v_bytes[..31].iter().any(|&b| b != 0) {
         output_slice[..32].fill(0);
-        output_slice[31] = 1;
-        return Bls12381Result::Success as c_int;
+        return Bn254Result::Success as c_int;
     }
+    let v = v_bytes[31];
+    let recovery_id = match v {
+        27 => 0u8,
+        28 => 1u8,
+        _ => {
+            output_slice[..32].fill(0);
+            return Bn254Result::Success as c_int;
+        }
+    };
 
-    for i in 0..num_pairs {
-        let offset = i * 384;
-        
-        // Parse G1 point (128 bytes)
-        let g1_x_bytes = &input_slice[offset..offset + 48];
-        let g1_y_bytes = &input_slice[offset + 48..offset + 96];
-
-        let g1_x = Fq::from_be_bytes_mod_order(g1_x_bytes);
-        let g1_y = Fq::from_be_bytes_mod_order(g1_y_bytes);
-
-        let g1_point = if g1_x.is_zero() && g1_y.is_zero() {
-            BlsG1Affine::zero()
-        } else {
-            match BlsG1Affine::new_unchecked(g1_x, g1_y) {
-                p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
-                _ => {
-                    output_slice[..32].fill(0);
-                    return Bls12381Result::Success as c_int;
-                }
-            }
-        };
-        g1_points.push(g1_point);
-
-        // Parse G2 point (256 bytes)
-        // G2 coordinates are in Fp2
-        let g2_x_c0_bytes = &input_slice[offset + 128..offset + 176];
-        let g2_x_c1_bytes = &input_slice[offset + 176..offset + 224];
-        let g2_y_c0_bytes = &input_slice[offset + 224..offset + 272];
-        let g2_y_c1_bytes = &input_slice[offset + 272..offset + 320];
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+    use sha3::{Digest, Keccak256};
 
-        let g2_x_c0 = Fq::from_be_bytes_mod_order(g2_x_c0_bytes);
-        let g2_x_c1 = Fq::from_be_bytes_mod_order(g2_x_c1_bytes);
-        let g2_y_c0 = Fq::from_be_bytes_mod_order(g2_y_c0_bytes);
-        let g2_y_c1 = Fq::from_be_bytes_mod_order(g2_y_c1_bytes);
+    let Ok(signature) = Signature::from_scalars(
+        <[u8; 32]>::try_from(r_bytes).unwrap(),
+        <[u8; 32]>::try_from(s_bytes).unwrap(),
+    ) else {
+        output_slice[..32].fill(0);
+        return Bn254Result::Success as c_int;
+    };
 
-        let g2_x = Fq2::new(g2_x_c0, g2_x_c1);
-        let g2_y = Fq2::new(g2_y_c0, g2_y_c1);
+    let Some(recovery_id) = RecoveryId::from_byte(recovery_id) else {
+        output_slice[..32].fill(0);
+        return Bn254Result::Success as c_int;
+    };
 
-        let g2_point = if g2_x.is_zero() && g2_y.is_zero() {
-            BlsG2Affine::zero()
-        } else {
-            match BlsG2Affine::new_unchecked(g2_x, g2_y) {
-                p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
-                _ => {
-                    output_slice[..32].fill(0);
-                    return Bls12381Result::Success as c_int;
-                }
-            }
-        };
-        g2_points.push(g2_point);
-    }
+    let Ok(verifying_key) =
+        VerifyingKey::recover_from_prehash(hash, &signature, recovery_id)
+    else {
+        output_slice[..32].fill(0);
+        return Bn254Result::Success as c_int;
+    };
 
-    // Compute multi-pairing
-    let pairing_result = Bls12_381::multi_pairing(&g1_points, &g2_points);
+    let encoded_point = verifying_key.to_encoded_point(false);
+    let pubkey_bytes = &encoded_point.as_bytes()[1..]; // drop the 0x04 prefix
 
-    // Check if result equals 1 (identity element in GT)
-    use ark_ec::pairing::PairingOutput;
-    let identity = PairingOutput::<Bls12_381>(Fq12::one());
-    let is_one = pairing_result == identity;
+    let address_hash = Keccak256::digest(pubkey_bytes);
 
-    // Set output
     output_slice[..32].fill(0);
-    if is_one {
-        output_slice[31] = 1;
-    }
+    output_slice[12..32].copy_from_slice(&address_hash[12..32]);
 
-    Bls12381Result::Success as c_int
+    Bn254Result::Success as c_int
 }
 
-/// Get the expected output size for BLS12-381 G1 operations
+/// Get the expected output size for ECRECOVER
 #[no_mangle]
-pub extern "C" fn bls12_381_g1_output_size() -> c_uint {
-    128
+pub extern "C" fn ecrecover_output_size() -> c_uint {
+    32
 }
 
-/// Get the expected output size for BLS12-381 pairing
+/// Validate ECRECOVER input format
 #[no_mangle]
-pub extern "C" fn bls12_381_pairing_output_size() -> c_uint {
-    32
-}
+pub extern "C" fn ecrecover_validate_input(input: *const c_uchar, input_len: c_uint) -> c_int {
+    if input.is_null() {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    if input_len < 128 {
+        return Bn254Result::InvalidInput as c_int;
+    }
+
+    Bn254Result::Success as c_int
+}
+
+/// Result codes for BLS12-381 operations
+#[repr(C)]
+pub enum Bls12381Result {
+    Success = 0,
+    InvalidInput = 1,
+    InvalidPoint = 2,
+    InvalidScalar = 3,
+    ComputationFailed = 4,
+}
+
+/// Strictly decode a 64-byte big-endian field element slab per EIP-2537: the
+/// top 16 bytes must be zero, and the remaining 48 bytes must encode a value
+/// strictly less than the BLS12-381 base field modulus `p`. Returns `None`
+/// for any non-canonical encoding instead of silently reducing it the way
+/// `Fq::from_be_bytes_mod_order` does.
+fn decode_fq_strict(slab: &[u8]) -> Option<ark_bls12_381::Fq> {
+    debug_assert_eq!(slab.len(), 64);
+
+    if slab[..16].iter().any(|&b| b != 0) {
+        return None;
+    }
+
+    let value_bytes = &slab[16..64];
+    let value = ark_bls12_381::Fq::from_be_bytes_mod_order(value_bytes);
+
+    // Reject non-canonical (>= p) encodings by checking the value round-trips
+    // back to the same bytes it was decoded from.
+    let reencoded_be = value.into_bigint().to_bytes_be();
+    let mut reencoded = [0u8; 48];
+    reencoded[48 - reencoded_be.len()..].copy_from_slice(&reencoded_be);
+    if reencoded != *value_bytes {
+        return None;
+    }
+
+    Some(value)
+}
+
+/// Strictly decode a 128-byte Fp2 slab (two 64-byte-padded components, `c0`
+/// then `c1`) per EIP-2537, applying [`decode_fq_strict`] component-wise.
+fn decode_fq2_strict(slab: &[u8]) -> Option<ark_bls12_381::Fq2> {
+    debug_assert_eq!(slab.len(), 128);
+    let c0 = decode_fq_strict(&slab[0..64])?;
+    let c1 = decode_fq_strict(&slab[64..128])?;
+    Some(ark_bls12_381::Fq2::new(c0, c1))
+}
+
+/// Perform BLS12-381 G1 addition
+///
+/// Input format (256 bytes), each coordinate padded to a 64-byte slab per
+/// EIP-2537 (top 16 bytes zero, low 48 bytes the big-endian value):
+/// - Bytes 0-63: first point x coordinate
+/// - Bytes 64-127: first point y coordinate
+/// - Bytes 128-191: second point x coordinate
+/// - Bytes 192-255: second point y coordinate
+///
+/// Output format (128 bytes):
+/// - Bytes 0-47: result x coordinate (big-endian)
+/// - Bytes 48-95: result y coordinate (big-endian)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_add(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len < 256 || output_len < 128 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let (Some(p1_x), Some(p1_y), Some(p2_x), Some(p2_y)) = (
+        decode_fq_strict(&input_slice[0..64]),
+        decode_fq_strict(&input_slice[64..128]),
+        decode_fq_strict(&input_slice[128..192]),
+        decode_fq_strict(&input_slice[192..256]),
+    ) else {
+        return Bls12381Result::InvalidInput as c_int;
+    };
+
+    // Check for point at infinity
+    let p1 = if p1_x.is_zero() && p1_y.is_zero() {
+        BlsG1Affine::zero()
+    } else {
+        match BlsG1Affine::new_unchecked(p1_x, p1_y) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..128].fill(0);
+                return Bls12381Result::Success as c_int;
+            }
+        }
+    };
+
+    let p2 = if p2_x.is_zero() && p2_y.is_zero() {
+        BlsG1Affine::zero()
+    } else {
+        match BlsG1Affine::new_unchecked(p2_x, p2_y) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..128].fill(0);
+                return Bls12381Result::Success as c_int;
+            }
+        }
+    };
+
+    // Perform addition
+    let result = (p1 + p2).into_affine();
+
+    // Handle point at infinity
+    if result.is_zero() {
+        output_slice[..128].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    // Convert result to bytes
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    let x_bytes = x_result.into_bigint().to_bytes_be();
+    let y_bytes = y_result.into_bigint().to_bytes_be();
+
+    // Pad and copy to output
+    output_slice[..128].fill(0);
+    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
+    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Perform BLS12-381 G1 scalar multiplication
+///
+/// Input format (160 bytes):
+/// - Bytes 0-63: x coordinate, 64-byte padded per EIP-2537
+/// - Bytes 64-127: y coordinate, 64-byte padded per EIP-2537
+/// - Bytes 128-159: scalar (big-endian, not padded)
+///
+/// Output format (128 bytes):
+/// - Bytes 0-47: result x coordinate (big-endian)
+/// - Bytes 48-95: result y coordinate (big-endian)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_mul(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len < 160 || output_len < 128 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let (Some(x_coord), Some(y_coord)) = (
+        decode_fq_strict(&input_slice[0..64]),
+        decode_fq_strict(&input_slice[64..128]),
+    ) else {
+        return Bls12381Result::InvalidInput as c_int;
+    };
+
+    // Parse scalar (32 bytes)
+    let scalar_bytes = &input_slice[128..160];
+
+    use ark_bls12_381::Fr;
+
+    let scalar = Fr::from_be_bytes_mod_order(scalar_bytes);
+
+    // Check for point at infinity
+    let point = if x_coord.is_zero() && y_coord.is_zero() {
+        BlsG1Affine::zero()
+    } else {
+        match BlsG1Affine::new_unchecked(x_coord, y_coord) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..128].fill(0);
+                return Bls12381Result::Success as c_int;
+            }
+        }
+    };
+
+    // Perform scalar multiplication
+    let result = (point * scalar).into_affine();
+
+    // Handle point at infinity
+    if result.is_zero() {
+        output_slice[..128].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    // Convert result to bytes
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    let x_bytes = x_result.into_bigint().to_bytes_be();
+    let y_bytes = y_result.into_bigint().to_bytes_be();
+
+    // Pad and copy to output
+    output_slice[..128].fill(0);
+    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
+    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Perform BLS12-381 G1 multi-scalar multiplication
+///
+/// Input format (variable, 160 * k bytes for k points):
+/// Each 160-byte group contains:
+/// - Bytes 0-63: x coordinate, 64-byte padded per EIP-2537
+/// - Bytes 64-127: y coordinate, 64-byte padded per EIP-2537
+/// - Bytes 128-159: scalar (big-endian, not padded)
+///
+/// Output format (128 bytes):
+/// - Bytes 0-47: result x coordinate (big-endian)
+/// - Bytes 48-95: result y coordinate (big-endian)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_multiexp(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len % 160 != 0 || output_len < 128 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    use ark_bls12_381::{Fr, G1Projective};
+    use ark_ec::VariableBaseMSM;
+
+    let num_pairs = (input_len as usize) / 160;
+    let mut points = Vec::with_capacity(num_pairs);
+    let mut scalars = Vec::with_capacity(num_pairs);
+
+    for i in 0..num_pairs {
+        let offset = i * 160;
+
+        let (Some(x_coord), Some(y_coord)) = (
+            decode_fq_strict(&input_slice[offset..offset + 64]),
+            decode_fq_strict(&input_slice[offset + 64..offset + 128]),
+        ) else {
+            return Bls12381Result::InvalidInput as c_int;
+        };
+
+        let scalar_bytes = &input_slice[offset + 128..offset + 160];
+        let scalar = Fr::from_be_bytes_mod_order(scalar_bytes);
+
+        // Skip point at infinity
+        if x_coord.is_zero() && y_coord.is_zero() {
+            continue;
+        }
+
+        let point = match BlsG1Affine::new_unchecked(x_coord, y_coord) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => continue,
+        };
+
+        points.push(point);
+        scalars.push(scalar);
+    }
+
+    // Perform multi-scalar multiplication
+    let result = if points.is_empty() {
+        BlsG1Affine::zero()
+    } else {
+        G1Projective::msm(&points, &scalars).unwrap().into_affine()
+    };
+
+    // Handle point at infinity
+    if result.is_zero() {
+        output_slice[..128].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    // Convert result to bytes
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    let x_bytes = x_result.into_bigint().to_bytes_be();
+    let y_bytes = y_result.into_bigint().to_bytes_be();
+
+    // Pad and copy to output
+    output_slice[..128].fill(0);
+    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
+    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Encode an Fq2 coordinate as two tightly-packed 48-byte big-endian halves
+/// (c0 then c1), matching the output convention already used by the G1
+/// functions in this file.
+fn write_fq2(out: &mut [u8], value: ark_bls12_381::Fq2) {
+    let c0_bytes = value.c0.into_bigint().to_bytes_be();
+    let c1_bytes = value.c1.into_bigint().to_bytes_be();
+    out[..48].fill(0);
+    out[48..96].fill(0);
+    out[48 - c0_bytes.len()..48].copy_from_slice(&c0_bytes);
+    out[96 - c1_bytes.len()..96].copy_from_slice(&c1_bytes);
+}
+
+/// Perform BLS12-381 G2 addition
+///
+/// Input format (512 bytes), each Fp2 coordinate as two 64-byte-padded slabs
+/// (c0 then c1) per EIP-2537:
+/// - Bytes 0-127: first point x (Fp2)
+/// - Bytes 128-255: first point y (Fp2)
+/// - Bytes 256-383: second point x (Fp2)
+/// - Bytes 384-511: second point y (Fp2)
+///
+/// Output format (192 bytes):
+/// - Bytes 0-95: result x (c0, c1, 48 bytes each)
+/// - Bytes 96-191: result y (c0, c1, 48 bytes each)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_add(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len < 512 || output_len < 192 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let (Some(p1_x), Some(p1_y), Some(p2_x), Some(p2_y)) = (
+        decode_fq2_strict(&input_slice[0..128]),
+        decode_fq2_strict(&input_slice[128..256]),
+        decode_fq2_strict(&input_slice[256..384]),
+        decode_fq2_strict(&input_slice[384..512]),
+    ) else {
+        return Bls12381Result::InvalidInput as c_int;
+    };
+
+    let p1 = if p1_x.is_zero() && p1_y.is_zero() {
+        BlsG2Affine::zero()
+    } else {
+        match BlsG2Affine::new_unchecked(p1_x, p1_y) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..192].fill(0);
+                return Bls12381Result::Success as c_int;
+            }
+        }
+    };
+
+    let p2 = if p2_x.is_zero() && p2_y.is_zero() {
+        BlsG2Affine::zero()
+    } else {
+        match BlsG2Affine::new_unchecked(p2_x, p2_y) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..192].fill(0);
+                return Bls12381Result::Success as c_int;
+            }
+        }
+    };
+
+    let result = (p1 + p2).into_affine();
+
+    if result.is_zero() {
+        output_slice[..192].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    write_fq2(&mut output_slice[0..96], *x_result);
+    write_fq2(&mut output_slice[96..192], *y_result);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Perform BLS12-381 G2 scalar multiplication
+///
+/// Input format (288 bytes):
+/// - Bytes 0-127: x (Fp2, two 64-byte-padded slabs)
+/// - Bytes 128-255: y (Fp2, two 64-byte-padded slabs)
+/// - Bytes 256-287: scalar (big-endian, not padded)
+///
+/// Output format (192 bytes):
+/// - Bytes 0-95: result x (c0, c1, 48 bytes each)
+/// - Bytes 96-191: result y (c0, c1, 48 bytes each)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_mul(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len < 288 || output_len < 192 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let (Some(x_coord), Some(y_coord)) = (
+        decode_fq2_strict(&input_slice[0..128]),
+        decode_fq2_strict(&input_slice[128..256]),
+    ) else {
+        return Bls12381Result::InvalidInput as c_int;
+    };
+
+    use ark_bls12_381::Fr;
+    let scalar = Fr::from_be_bytes_mod_order(&input_slice[256..288]);
+
+    let point = if x_coord.is_zero() && y_coord.is_zero() {
+        BlsG2Affine::zero()
+    } else {
+        match BlsG2Affine::new_unchecked(x_coord, y_coord) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => {
+                output_slice[..192].fill(0);
+                return Bls12381Result::Success as c_int;
+            }
+        }
+    };
+
+    let result = (point * scalar).into_affine();
+
+    if result.is_zero() {
+        output_slice[..192].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    write_fq2(&mut output_slice[0..96], *x_result);
+    write_fq2(&mut output_slice[96..192], *y_result);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Perform BLS12-381 G2 multi-scalar multiplication
+///
+/// Input format (variable, 288 * k bytes for k points):
+/// Each 288-byte group contains:
+/// - Bytes 0-255: point (x, y each Fp2, two 64-byte-padded slabs)
+/// - Bytes 256-287: scalar (big-endian, not padded)
+///
+/// Output format (192 bytes):
+/// - Bytes 0-95: result x (c0, c1, 48 bytes each)
+/// - Bytes 96-191: result y (c0, c1, 48 bytes each)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_multiexp(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len % 288 != 0 || output_len < 192 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    use ark_bls12_381::{Fr, G2Projective};
+    use ark_ec::VariableBaseMSM;
+
+    let num_pairs = (input_len as usize) / 288;
+    let mut points = Vec::with_capacity(num_pairs);
+    let mut scalars = Vec::with_capacity(num_pairs);
+
+    for i in 0..num_pairs {
+        let offset = i * 288;
+
+        let (Some(x_coord), Some(y_coord)) = (
+            decode_fq2_strict(&input_slice[offset..offset + 128]),
+            decode_fq2_strict(&input_slice[offset + 128..offset + 256]),
+        ) else {
+            return Bls12381Result::InvalidInput as c_int;
+        };
+
+        let scalar = Fr::from_be_bytes_mod_order(&input_slice[offset + 256..offset + 288]);
+
+        if x_coord.is_zero() && y_coord.is_zero() {
+            continue;
+        }
+
+        let point = match BlsG2Affine::new_unchecked(x_coord, y_coord) {
+            p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+            _ => continue,
+        };
+
+        points.push(point);
+        scalars.push(scalar);
+    }
+
+    let result = if points.is_empty() {
+        BlsG2Affine::zero()
+    } else {
+        G2Projective::msm(&points, &scalars).unwrap().into_affine()
+    };
+
+    if result.is_zero() {
+        output_slice[..192].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    let x_result = result.x().expect("x coordinate should exist");
+    let y_result = result.y().expect("y coordinate should exist");
+
+    write_fq2(&mut output_slice[0..96], *x_result);
+    write_fq2(&mut output_slice[96..192], *y_result);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Get the expected output size for BLS12-381 G2 operations
+#[no_mangle]
+pub extern "C" fn bls12_381_g2_output_size() -> c_uint {
+    192
+}
+
+/// Perform BLS12-381 pairing check
+///
+/// Input format (variable, 384 * k bytes for k pairs):
+/// Each 384-byte group contains, with every coordinate 64-byte padded per
+/// EIP-2537:
+/// - Bytes 0-127: G1 point (x, y)
+/// - Bytes 128-383: G2 point (x.c0, x.c1, y.c0, y.c1 in Fp2)
+///
+/// Output format (32 bytes):
+/// - 32-byte boolean result (0x00...00 for false, 0x00...01 for true)
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_pairing(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len % 384 != 0 || output_len < 32 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    use ark_bls12_381::Fq12;
+
+    let num_pairs = (input_len as usize) / 384;
+    let mut g1_points = Vec::with_capacity(num_pairs);
+    let mut g2_points = Vec::with_capacity(num_pairs);
+
+    // Handle empty input (should return true according to EIP-2537)
+    if input_len == 0 {
+        output_slice[..32].fill(0);
+        output_slice[31] = 1;
+        return Bls12381Result::Success as c_int;
+    }
+
+    for i in 0..num_pairs {
+        let offset = i * 384;
+
+        // Parse G1 point (128 bytes)
+        let (Some(g1_x), Some(g1_y)) = (
+            decode_fq_strict(&input_slice[offset..offset + 64]),
+            decode_fq_strict(&input_slice[offset + 64..offset + 128]),
+        ) else {
+            return Bls12381Result::InvalidInput as c_int;
+        };
+
+        let g1_point = if g1_x.is_zero() && g1_y.is_zero() {
+            BlsG1Affine::zero()
+        } else {
+            match BlsG1Affine::new_unchecked(g1_x, g1_y) {
+                p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+                _ => {
+                    output_slice[..32].fill(0);
+                    return Bls12381Result::Success as c_int;
+                }
+            }
+        };
+        g1_points.push(g1_point);
+
+        // Parse G2 point (256 bytes), each Fp2 coordinate as two 64-byte slabs
+        let (Some(g2_x), Some(g2_y)) = (
+            decode_fq2_strict(&input_slice[offset + 128..offset + 256]),
+            decode_fq2_strict(&input_slice[offset + 256..offset + 384]),
+        ) else {
+            return Bls12381Result::InvalidInput as c_int;
+        };
+
+        let g2_point = if g2_x.is_zero() && g2_y.is_zero() {
+            BlsG2Affine::zero()
+        } else {
+            match BlsG2Affine::new_unchecked(g2_x, g2_y) {
+                p if p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve() => p,
+                _ => {
+                    output_slice[..32].fill(0);
+                    return Bls12381Result::Success as c_int;
+                }
+            }
+        };
+        g2_points.push(g2_point);
+    }
+
+    // Compute multi-pairing
+    let pairing_result = Bls12_381::multi_pairing(&g1_points, &g2_points);
+
+    // Check if result equals 1 (identity element in GT)
+    use ark_ec::pairing::PairingOutput;
+    let identity = PairingOutput::<Bls12_381>(Fq12::one());
+    let is_one = pairing_result == identity;
+
+    // Set output
+    output_slice[..32].fill(0);
+    if is_one {
+        output_slice[31] = 1;
+    }
+
+    Bls12381Result::Success as c_int
+}
+
+/// Get the expected output size for BLS12-381 G1 operations
+#[no_mangle]
+pub extern "C" fn bls12_381_g1_output_size() -> c_uint {
+    128
+}
+
+/// Get the expected output size for BLS12-381 pairing
+#[no_mangle]
+pub extern "C" fn bls12_381_pairing_output_size() -> c_uint {
+    32
+}
+
+/// Base gas cost of a single BLS12-381 G1 scalar multiplication.
+pub const BLS12_381_G1_MUL_BASE_GAS: u64 = 12_000;
+/// Base gas cost of a single BLS12-381 G2 scalar multiplication.
+pub const BLS12_381_G2_MUL_BASE_GAS: u64 = 22_500;
+/// Base gas cost of a single BLS12-381 pairing.
+pub const BLS12_381_PAIRING_BASE_GAS: u64 = 65_000;
+/// Per-pair gas cost of a BLS12-381 pairing.
+pub const BLS12_381_PAIRING_PER_PAIR_GAS: u64 = 43_000;
+
+/// The EIP-2537 multiexp discount table: `discount[k - 1]` is the per-mille
+/// discount factor applied when summing `k` scalar multiplications via MSM
+/// instead of `k` independent multiplications, for `k` in `1..=128`. Callers
+/// with more than 128 pairs clamp to the final (largest-discount) entry.
+const MULTIEXP_DISCOUNT_TABLE: [u64; 128] = [
+    1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669,
+    665, 661, 658, 654, 651, 648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617,
+    615, 613, 611, 609, 608, 606, 604, 603, 601, 599, 598, 596, 595, 593, 592, 591, 589, 588, 586,
+    585, 584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570, 569, 568, 567, 566, 565, 564,
+    563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547, 546, 545,
+    545, 544, 543, 542, 541, 540, 539, 539, 538, 537, 536, 535, 535, 534, 533, 532, 531, 531, 530,
+    529, 528, 528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// Look up the EIP-2537 multiexp discount for `k` pairs, clamped to the
+/// final table entry for `k > 128`.
+fn multiexp_discount(k: usize) -> u64 {
+    if k == 0 {
+        return 0;
+    }
+    let index = (k - 1).min(MULTIEXP_DISCOUNT_TABLE.len() - 1);
+    MULTIEXP_DISCOUNT_TABLE[index]
+}
+
+/// `k * base_per_pair * discount(k) / 1000`, the EIP-2537 MSM gas formula.
+fn multiexp_gas(k: usize, base_per_pair: u64) -> u64 {
+    (k as u64) * base_per_pair * multiexp_discount(k) / 1000
+}
+
+/// Compute the EIP-2537 gas cost of a `bls12_381_g1_multiexp` call given its
+/// input length.
+#[no_mangle]
+pub extern "C" fn bls12_381_g1_multiexp_gas(input_len: c_uint) -> c_uint {
+    let k = (input_len as usize) / 160;
+    multiexp_gas(k, BLS12_381_G1_MUL_BASE_GAS) as c_uint
+}
+
+/// Compute the EIP-2537 gas cost of a `bls12_381_g2_multiexp` call given its
+/// input length.
+#[no_mangle]
+pub extern "C" fn bls12_381_g2_multiexp_gas(input_len: c_uint) -> c_uint {
+    let k = (input_len as usize) / 288;
+    multiexp_gas(k, BLS12_381_G2_MUL_BASE_GAS) as c_uint
+}
+
+/// Compute the EIP-2537 gas cost of a `bls12_381_pairing` call given its
+/// input length.
+#[no_mangle]
+pub extern "C" fn bls12_381_pairing_gas(input_len: c_uint) -> c_uint {
+    let num_pairs = (input_len as u64) / 384;
+    (BLS12_381_PAIRING_BASE_GAS + BLS12_381_PAIRING_PER_PAIR_GAS * num_pairs) as c_uint
+}
+
+/// Validate a `bls12_381_g1_add` input: length, then field range on all
+/// four coordinates. Off-curve or off-subgroup points are *not* flagged
+/// here — `bls12_381_g1_add` itself treats those as "zero output, success"
+/// rather than an error, so rejecting them here would make this validator
+/// stricter than the operation it's meant to pre-check.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_add_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 256 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let coords_ok = decode_fq_strict(&input_slice[0..64]).is_some()
+        && decode_fq_strict(&input_slice[64..128]).is_some()
+        && decode_fq_strict(&input_slice[128..192]).is_some()
+        && decode_fq_strict(&input_slice[192..256]).is_some();
+    if !coords_ok {
+        return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Validate a `bls12_381_g1_mul` input. See
+/// [`bls12_381_g1_add_validate_input`] for why curve/subgroup membership
+/// isn't checked here.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_mul_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 160 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let coords_ok = decode_fq_strict(&input_slice[0..64]).is_some()
+        && decode_fq_strict(&input_slice[64..128]).is_some();
+    if !coords_ok {
+        return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Validate a `bls12_381_g1_multiexp` input: length a multiple of 160, then
+/// field range on every point's coordinates. See
+/// [`bls12_381_g1_add_validate_input`] for why curve/subgroup membership
+/// isn't checked here.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_multiexp_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len % 160 != 0 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let num_pairs = (input_len as usize) / 160;
+    for i in 0..num_pairs {
+        let offset = i * 160;
+        let coords_ok = decode_fq_strict(&input_slice[offset..offset + 64]).is_some()
+            && decode_fq_strict(&input_slice[offset + 64..offset + 128]).is_some();
+        if !coords_ok {
+            return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+        }
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Validate a `bls12_381_g2_add` input. See
+/// [`bls12_381_g1_add_validate_input`] for why curve/subgroup membership
+/// isn't checked here.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_add_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 512 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let coords_ok = decode_fq2_strict(&input_slice[0..128]).is_some()
+        && decode_fq2_strict(&input_slice[128..256]).is_some()
+        && decode_fq2_strict(&input_slice[256..384]).is_some()
+        && decode_fq2_strict(&input_slice[384..512]).is_some();
+    if !coords_ok {
+        return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Validate a `bls12_381_g2_mul` input. See
+/// [`bls12_381_g1_add_validate_input`] for why curve/subgroup membership
+/// isn't checked here.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_mul_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 288 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let coords_ok = decode_fq2_strict(&input_slice[0..128]).is_some()
+        && decode_fq2_strict(&input_slice[128..256]).is_some();
+    if !coords_ok {
+        return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Validate a `bls12_381_g2_multiexp` input: length a multiple of 288, then
+/// field range on every point's coordinates. See
+/// [`bls12_381_g1_add_validate_input`] for why curve/subgroup membership
+/// isn't checked here.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_multiexp_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len % 288 != 0 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let num_pairs = (input_len as usize) / 288;
+    for i in 0..num_pairs {
+        let offset = i * 288;
+        let coords_ok = decode_fq2_strict(&input_slice[offset..offset + 128]).is_some()
+            && decode_fq2_strict(&input_slice[offset + 128..offset + 256]).is_some();
+        if !coords_ok {
+            return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+        }
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Validate a `bls12_381_pairing` input: length a multiple of 384, then
+/// field range on every pair's G1 and G2 coordinates. The empty input
+/// (`input_len == 0`) is valid, matching `bls12_381_pairing`'s own
+/// EIP-2537 empty-input handling. See [`bls12_381_g1_add_validate_input`]
+/// for why curve/subgroup membership isn't checked here.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_pairing_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len % 384 != 0 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let num_pairs = (input_len as usize) / 384;
+    for i in 0..num_pairs {
+        let offset = i * 384;
+        let coords_ok = decode_fq_strict(&input_slice[offset..offset + 64]).is_some()
+            && decode_fq_strict(&input_slice[offset + 64..offset + 128]).is_some()
+            && decode_fq2_strict(&input_slice[offset + 128..offset + 256]).is_some()
+            && decode_fq2_strict(&input_slice[offset + 256..offset + 384]).is_some();
+        if !coords_ok {
+            return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+        }
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Map a field element to a G1 point (EIP-2537 `MAP_FP_TO_G1`)
+///
+/// Input format (64 bytes): a single Fp element `u`, 64-byte padded per
+/// EIP-2537.
+///
+/// Output format (128 bytes): the resulting G1 point, x then y, 48 bytes
+/// each tightly packed, matching the convention used by the other G1
+/// functions in this file.
+///
+/// Implemented with arkworks' simplified-SWU-with-isogeny hash-to-curve
+/// machinery (the same machinery backing `WBMap`/`MapToCurveBasedHasher`),
+/// which applies the standard SSWU map onto the 11-isogenous auxiliary
+/// curve, the 11-isogeny back to the BLS12-381 G1 curve, and then clears
+/// the cofactor to land in the correct subgroup.
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn map_fp_to_g1(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len < 64 || output_len < 128 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let Some(u) = decode_fq_strict(&input_slice[0..64]) else {
+        return Bls12381Result::InvalidInput as c_int;
+    };
+
+    use ark_bls12_381::g1::Config as G1Config;
+    use ark_ec::hashing::curve_maps::wb::WBMap;
+    use ark_ec::hashing::map_to_curve_hasher::MapToCurve;
+    use ark_ec::short_weierstrass::SWCurveConfig;
+
+    let map = WBMap::<G1Config>::new().expect("BLS12-381 G1 isogeny map parameters are valid");
+    let point = map
+        .map_to_curve(u)
+        .expect("SSWU map is defined for every field element");
+    let cleared = G1Config::clear_cofactor(&point);
+
+    if cleared.is_zero() {
+        output_slice[..128].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    let x_result = cleared.x().expect("x coordinate should exist");
+    let y_result = cleared.y().expect("y coordinate should exist");
+
+    let x_bytes = x_result.into_bigint().to_bytes_be();
+    let y_bytes = y_result.into_bigint().to_bytes_be();
+
+    output_slice[..128].fill(0);
+    output_slice[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
+    output_slice[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Validate a `map_fp_to_g1` input: length, then field range on `u`. The
+/// SSWU map is defined for every field element, so unlike the `*_add`/
+/// `*_mul`/`*_multiexp` validators there's no curve/subgroup check to skip
+/// here either — a field-range-valid `u` always maps to a valid point.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn map_fp_to_g1_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 64 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    if decode_fq_strict(&input_slice[0..64]).is_none() {
+        return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Map an Fp2 element to a G2 point (EIP-2537 `MAP_FP2_TO_G2`)
+///
+/// Input format (128 bytes): a single Fp2 element `u` (c0, c1), each
+/// 64-byte padded per EIP-2537.
+///
+/// Output format (192 bytes): the resulting G2 point, x then y, each as two
+/// tightly-packed 48-byte halves (c0, c1), matching [`bls12_381_g2_add`]'s
+/// output convention.
+///
+/// Follows the same structure as [`map_fp_to_g1`] over Fp2: the SSWU map
+/// onto the auxiliary curve, the G2-specific isogeny, and cofactor
+/// clearing via the `(x^2 - x - 1)`-style G2 cofactor.
+///
+/// # Safety
+///
+/// This function dereferences raw pointers and requires:
+/// - `input` must be valid for reads of `input_len` bytes
+/// - `output` must be valid for writes of `output_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn map_fp2_to_g2(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    if input_len < 128 || output_len < 192 {
+        return Bls12381Result::InvalidInput as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let Some(u) = decode_fq2_strict(&input_slice[0..128]) else {
+        return Bls12381Result::InvalidInput as c_int;
+    };
+
+    use ark_bls12_381::g2::Config as G2Config;
+    use ark_ec::hashing::curve_maps::wb::WBMap;
+    use ark_ec::hashing::map_to_curve_hasher::MapToCurve;
+    use ark_ec::short_weierstrass::SWCurveConfig;
+
+    let map = WBMap::<G2Config>::new().expect("BLS12-381 G2 isogeny map parameters are valid");
+    let point = map
+        .map_to_curve(u)
+        .expect("SSWU map is defined for every field element");
+    let cleared = G2Config::clear_cofactor(&point);
+
+    if cleared.is_zero() {
+        output_slice[..192].fill(0);
+        return Bls12381Result::Success as c_int;
+    }
+
+    let x_result = cleared.x().expect("x coordinate should exist");
+    let y_result = cleared.y().expect("y coordinate should exist");
+
+    write_fq2(&mut output_slice[0..96], *x_result);
+    write_fq2(&mut output_slice[96..192], *y_result);
+
+    Bls12381Result::Success as c_int
+}
+
+/// Validate a `map_fp2_to_g2` input. See [`map_fp_to_g1_validate_input`].
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn map_fp2_to_g2_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 128 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    if decode_fq2_strict(&input_slice[0..128]).is_none() {
+        return Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Get the expected output size for `map_fp_to_g1`.
+#[no_mangle]
+pub extern "C" fn map_fp_to_g1_output_size() -> c_uint {
+    128
+}
+
+/// Get the expected output size for `map_fp2_to_g2`.
+#[no_mangle]
+pub extern "C" fn map_fp2_to_g2_output_size() -> c_uint {
+    192
+}
+
+// --- BLS12-381 compressed/uncompressed codec and aggregate-verify ---
+//
+// The functions above speak EIP-2537's wire format (each field element
+// 64-byte padded on input, tightly packed 48-byte halves on output). The
+// functions below instead model the compressed/uncompressed point encoding
+// and status-reporting convention used by mature Ethereum consensus BLS
+// bindings: a 48-byte (G1) or 96-byte (G2) compressed point with the
+// compression/infinity/sign flags packed into the top 3 bits of the first
+// byte, and a [`Bls12381Code`] distinguishing bad encodings from off-curve
+// points from off-subgroup points. Uncompressed points here use the same
+// tight 96-byte (G1) / 192-byte (G2) layout already written by
+// `bls12_381_g1_add` and `bls12_381_g2_add`.
+
+const BLS_COMPRESSION_FLAG: u8 = 0x80;
+const BLS_INFINITY_FLAG: u8 = 0x40;
+const BLS_SORT_FLAG: u8 = 0x20;
+const BLS_FLAG_MASK: u8 = 0xe0;
+
+/// Decode a tight (unpadded) 48-byte big-endian field element, rejecting
+/// non-canonical (`>= p`) encodings the same way [`decode_fq_strict`] does
+/// for the 64-byte-padded EIP-2537 encoding.
+fn decode_bls_fq_tight(bytes: &[u8]) -> Option<ark_bls12_381::Fq> {
+    debug_assert_eq!(bytes.len(), 48);
+    let value = ark_bls12_381::Fq::from_be_bytes_mod_order(bytes);
+    let reencoded_be = value.into_bigint().to_bytes_be();
+    let mut reencoded = [0u8; 48];
+    reencoded[48 - reencoded_be.len()..].copy_from_slice(&reencoded_be);
+    if reencoded != *bytes {
+        return None;
+    }
+    Some(value)
+}
+
+/// Whether `value` is the lexicographically larger of `{value, -value}`,
+/// per the sign convention mature BLS bindings use to pick which square
+/// root a compressed point's sort flag refers to.
+fn fq_is_lexicographically_largest(value: &ark_bls12_381::Fq) -> bool {
+    let neg = -*value;
+    value.into_bigint() > neg.into_bigint()
+}
+
+/// Fp2 analogue of [`fq_is_lexicographically_largest`]: compares `c1`
+/// first (the more significant component), falling back to `c0`.
+fn fq2_is_lexicographically_largest(value: &ark_bls12_381::Fq2) -> bool {
+    let neg = -*value;
+    if value.c1 != neg.c1 {
+        value.c1.into_bigint() > neg.c1.into_bigint()
+    } else {
+        value.c0.into_bigint() > neg.c0.into_bigint()
+    }
+}
+
+/// Decode a tight 96-byte uncompressed G1 encoding (x then y, 48 bytes
+/// each), validating field range, curve membership, and subgroup
+/// membership. `Ok(None)` is the point at infinity (encoded as all zero).
+fn decode_g1_uncompressed_tight(bytes: &[u8]) -> Result<Option<BlsG1Affine>, Bls12381Code> {
+    debug_assert_eq!(bytes.len(), 96);
+    let (Some(x), Some(y)) = (
+        decode_bls_fq_tight(&bytes[0..48]),
+        decode_bls_fq_tight(&bytes[48..96]),
+    ) else {
+        return Err(Bls12381Code::COORDINATE_OUT_OF_RANGE);
+    };
+
+    if x.is_zero() && y.is_zero() {
+        return Ok(None);
+    }
+
+    let point = BlsG1Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        return Err(Bls12381Code::NOT_ON_CURVE);
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Bls12381Code::NOT_IN_SUBGROUP);
+    }
+    Ok(Some(point))
+}
+
+/// G2 analogue of [`decode_g1_uncompressed_tight`] over the 192-byte tight
+/// encoding (x then y, each an Fp2 as two tightly-packed 48-byte halves).
+fn decode_g2_uncompressed_tight(bytes: &[u8]) -> Result<Option<BlsG2Affine>, Bls12381Code> {
+    debug_assert_eq!(bytes.len(), 192);
+    let (Some(x_c0), Some(x_c1), Some(y_c0), Some(y_c1)) = (
+        decode_bls_fq_tight(&bytes[0..48]),
+        decode_bls_fq_tight(&bytes[48..96]),
+        decode_bls_fq_tight(&bytes[96..144]),
+        decode_bls_fq_tight(&bytes[144..192]),
+    ) else {
+        return Err(Bls12381Code::COORDINATE_OUT_OF_RANGE);
+    };
+
+    let x = ark_bls12_381::Fq2::new(x_c0, x_c1);
+    let y = ark_bls12_381::Fq2::new(y_c0, y_c1);
+    if x.is_zero() && y.is_zero() {
+        return Ok(None);
+    }
+
+    let point = BlsG2Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        return Err(Bls12381Code::NOT_ON_CURVE);
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Bls12381Code::NOT_IN_SUBGROUP);
+    }
+    Ok(Some(point))
+}
+
+fn encode_g1_compressed(point: Option<BlsG1Affine>, out: &mut [u8]) {
+    debug_assert_eq!(out.len(), 48);
+    out.fill(0);
+    let Some(point) = point else {
+        out[0] = BLS_COMPRESSION_FLAG | BLS_INFINITY_FLAG;
+        return;
+    };
+    let x_bytes = point
+        .x()
+        .expect("finite point has an x coordinate")
+        .into_bigint()
+        .to_bytes_be();
+    out[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
+    out[0] |= BLS_COMPRESSION_FLAG;
+    if fq_is_lexicographically_largest(point.y().expect("finite point has a y coordinate")) {
+        out[0] |= BLS_SORT_FLAG;
+    }
+}
+
+fn encode_g1_uncompressed(point: Option<BlsG1Affine>, out: &mut [u8]) {
+    debug_assert_eq!(out.len(), 96);
+    out.fill(0);
+    let Some(point) = point else { return };
+    let x_bytes = point
+        .x()
+        .expect("finite point has an x coordinate")
+        .into_bigint()
+        .to_bytes_be();
+    let y_bytes = point
+        .y()
+        .expect("finite point has a y coordinate")
+        .into_bigint()
+        .to_bytes_be();
+    out[48 - x_bytes.len()..48].copy_from_slice(&x_bytes);
+    out[96 - y_bytes.len()..96].copy_from_slice(&y_bytes);
+}
+
+fn encode_g2_compressed(point: Option<BlsG2Affine>, out: &mut [u8]) {
+    debug_assert_eq!(out.len(), 96);
+    out.fill(0);
+    let Some(point) = point else {
+        out[0] = BLS_COMPRESSION_FLAG | BLS_INFINITY_FLAG;
+        return;
+    };
+    // Component order (c0 then c1) matches this file's other tight G2
+    // encodings (`write_fq2`, `decode_fq2_strict`), rather than the c1-then-c0
+    // order some other BLS bindings use for compressed G2.
+    write_fq2(&mut out[0..96], *point.x().expect("finite point has an x coordinate"));
+    out[0] |= BLS_COMPRESSION_FLAG;
+    if fq2_is_lexicographically_largest(point.y().expect("finite point has a y coordinate")) {
+        out[0] |= BLS_SORT_FLAG;
+    }
+}
+
+fn encode_g2_uncompressed(point: Option<BlsG2Affine>, out: &mut [u8]) {
+    debug_assert_eq!(out.len(), 192);
+    out.fill(0);
+    let Some(point) = point else { return };
+    write_fq2(&mut out[0..96], *point.x().expect("finite point has an x coordinate"));
+    write_fq2(&mut out[96..192], *point.y().expect("finite point has a y coordinate"));
+}
+
+/// Recover a G1 point's y-coordinate from its x-coordinate and a sort flag,
+/// via `y^2 = x^3 + B` over the base field.
+fn recover_g1_y(x: ark_bls12_381::Fq, sort_flag: bool) -> Option<ark_bls12_381::Fq> {
+    use ark_ec::short_weierstrass::SWCurveConfig;
+    use ark_ff::Field;
+    let b = <ark_bls12_381::g1::Config as SWCurveConfig>::COEFF_B;
+    let y_squared = x * x * x + b;
+    let y = y_squared.sqrt()?;
+    let neg_y = -y;
+    Some(if fq_is_lexicographically_largest(&y) == sort_flag {
+        y
+    } else {
+        neg_y
+    })
+}
+
+/// G2 analogue of [`recover_g1_y`].
+fn recover_g2_y(x: ark_bls12_381::Fq2, sort_flag: bool) -> Option<ark_bls12_381::Fq2> {
+    use ark_ec::short_weierstrass::SWCurveConfig;
+    use ark_ff::Field;
+    let b = <ark_bls12_381::g2::Config as SWCurveConfig>::COEFF_B;
+    let y_squared = x * x * x + b;
+    let y = y_squared.sqrt()?;
+    let neg_y = -y;
+    Some(if fq2_is_lexicographically_largest(&y) == sort_flag {
+        y
+    } else {
+        neg_y
+    })
+}
+
+fn decode_g1_compressed(bytes: &[u8]) -> Result<Option<BlsG1Affine>, Bls12381Code> {
+    debug_assert_eq!(bytes.len(), 48);
+    let flags = bytes[0] & BLS_FLAG_MASK;
+    if flags & BLS_COMPRESSION_FLAG == 0 {
+        return Err(Bls12381Code::BAD_COMPRESSION_FLAG);
+    }
+
+    if flags & BLS_INFINITY_FLAG != 0 {
+        if flags & BLS_SORT_FLAG != 0 || bytes[0] & !BLS_FLAG_MASK != 0 || bytes[1..].iter().any(|&b| b != 0) {
+            return Err(Bls12381Code::BAD_INFINITY_ENCODING);
+        }
+        return Ok(None);
+    }
+
+    let mut x_bytes = [0u8; 48];
+    x_bytes.copy_from_slice(bytes);
+    x_bytes[0] &= !BLS_FLAG_MASK;
+    let Some(x) = decode_bls_fq_tight(&x_bytes) else {
+        return Err(Bls12381Code::COORDINATE_OUT_OF_RANGE);
+    };
+
+    let Some(y) = recover_g1_y(x, flags & BLS_SORT_FLAG != 0) else {
+        return Err(Bls12381Code::NOT_ON_CURVE);
+    };
+
+    let point = BlsG1Affine::new_unchecked(x, y);
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Bls12381Code::NOT_IN_SUBGROUP);
+    }
+    Ok(Some(point))
+}
+
+fn decode_g2_compressed(bytes: &[u8]) -> Result<Option<BlsG2Affine>, Bls12381Code> {
+    debug_assert_eq!(bytes.len(), 96);
+    let flags = bytes[0] & BLS_FLAG_MASK;
+    if flags & BLS_COMPRESSION_FLAG == 0 {
+        return Err(Bls12381Code::BAD_COMPRESSION_FLAG);
+    }
+
+    if flags & BLS_INFINITY_FLAG != 0 {
+        if flags & BLS_SORT_FLAG != 0 || bytes[0] & !BLS_FLAG_MASK != 0 || bytes[1..].iter().any(|&b| b != 0) {
+            return Err(Bls12381Code::BAD_INFINITY_ENCODING);
+        }
+        return Ok(None);
+    }
+
+    let mut x_c0_bytes = [0u8; 48];
+    x_c0_bytes.copy_from_slice(&bytes[0..48]);
+    x_c0_bytes[0] &= !BLS_FLAG_MASK;
+    let (Some(x_c0), Some(x_c1)) = (
+        decode_bls_fq_tight(&x_c0_bytes),
+        decode_bls_fq_tight(&bytes[48..96]),
+    ) else {
+        return Err(Bls12381Code::COORDINATE_OUT_OF_RANGE);
+    };
+    let x = ark_bls12_381::Fq2::new(x_c0, x_c1);
+
+    let Some(y) = recover_g2_y(x, flags & BLS_SORT_FLAG != 0) else {
+        return Err(Bls12381Code::NOT_ON_CURVE);
+    };
+
+    let point = BlsG2Affine::new_unchecked(x, y);
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Bls12381Code::NOT_IN_SUBGROUP);
+    }
+    Ok(Some(point))
+}
+
+/// Compress a BLS12-381 G1 point from its tight 96-byte uncompressed
+/// encoding (x then y, 48 bytes each — the layout [`bls12_381_g1_add`] and
+/// friends write) into the 48-byte compressed encoding: the top 3 bits of
+/// the first byte carry the compression, infinity, and y-sign flags, per
+/// the convention used by mature Ethereum BLS bindings.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes and `output` for
+/// writes of `output_len` bytes, when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_compress(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 96 || output_len < 48 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let point = match decode_g1_uncompressed_tight(&input_slice[0..96]) {
+        Ok(point) => point,
+        Err(code) => return code.raw() as c_int,
+    };
+
+    encode_g1_compressed(point, &mut output_slice[0..48]);
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Decompress a BLS12-381 G1 point from its 48-byte compressed encoding
+/// into the tight 96-byte uncompressed encoding (x then y, 48 bytes each).
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes and `output` for
+/// writes of `output_len` bytes, when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_decompress(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 48 || output_len < 96 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let point = match decode_g1_compressed(&input_slice[0..48]) {
+        Ok(point) => point,
+        Err(code) => return code.raw() as c_int,
+    };
+
+    encode_g1_uncompressed(point, &mut output_slice[0..96]);
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// G2 analogue of [`bls12_381_g1_compress`]: tight 192-byte uncompressed
+/// encoding in, 96-byte compressed encoding out.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes and `output` for
+/// writes of `output_len` bytes, when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_compress(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 192 || output_len < 96 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let point = match decode_g2_uncompressed_tight(&input_slice[0..192]) {
+        Ok(point) => point,
+        Err(code) => return code.raw() as c_int,
+    };
+
+    encode_g2_compressed(point, &mut output_slice[0..96]);
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// G2 analogue of [`bls12_381_g1_decompress`]: 96-byte compressed encoding
+/// in, tight 192-byte uncompressed encoding out.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes and `output` for
+/// writes of `output_len` bytes, when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_decompress(
+    input: *const c_uchar,
+    input_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if input.is_null() || output.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 96 || output_len < 192 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    let point = match decode_g2_compressed(&input_slice[0..96]) {
+        Ok(point) => point,
+        Err(code) => return code.raw() as c_int,
+    };
+
+    encode_g2_uncompressed(point, &mut output_slice[0..192]);
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+#[no_mangle]
+pub extern "C" fn bls12_381_g1_compress_output_size() -> c_uint {
+    48
+}
+
+#[no_mangle]
+pub extern "C" fn bls12_381_g1_decompress_output_size() -> c_uint {
+    96
+}
+
+#[no_mangle]
+pub extern "C" fn bls12_381_g2_compress_output_size() -> c_uint {
+    96
+}
+
+#[no_mangle]
+pub extern "C" fn bls12_381_g2_decompress_output_size() -> c_uint {
+    192
+}
+
+/// Validate a `bls12_381_g1_compress` input: full field-range, on-curve,
+/// and subgroup checks on the tight uncompressed encoding.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_compress_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 96 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    match decode_g1_uncompressed_tight(&input_slice[0..96]) {
+        Ok(_) => Bls12381Code::SUCCESS.raw() as c_int,
+        Err(code) => code.raw() as c_int,
+    }
+}
+
+/// Validate a `bls12_381_g1_decompress` input: flag bits, field range,
+/// on-curve, and subgroup checks on the compressed encoding.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_decompress_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 48 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    match decode_g1_compressed(&input_slice[0..48]) {
+        Ok(_) => Bls12381Code::SUCCESS.raw() as c_int,
+        Err(code) => code.raw() as c_int,
+    }
+}
+
+/// Validate a `bls12_381_g2_compress` input. See
+/// [`bls12_381_g1_compress_validate_input`].
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_compress_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 192 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    match decode_g2_uncompressed_tight(&input_slice[0..192]) {
+        Ok(_) => Bls12381Code::SUCCESS.raw() as c_int,
+        Err(code) => code.raw() as c_int,
+    }
+}
+
+/// Validate a `bls12_381_g2_decompress` input. See
+/// [`bls12_381_g1_decompress_validate_input`].
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_decompress_validate_input(
+    input: *const c_uchar,
+    input_len: c_uint,
+) -> c_int {
+    if input.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if input_len < 96 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    let input_slice = std::slice::from_raw_parts(input, input_len as usize);
+    match decode_g2_compressed(&input_slice[0..96]) {
+        Ok(_) => Bls12381Code::SUCCESS.raw() as c_int,
+        Err(code) => code.raw() as c_int,
+    }
+}
+
+/// Aggregate-verify a BLS signature against `n` (pubkey, message) pairs
+/// using a single aggregated G2 signature — the "AggregateVerify" shape
+/// used by mature Ethereum consensus BLS bindings for the min-pubkey-size
+/// ciphersuite (48-byte G1 pubkeys, 96-byte G2 signatures).
+///
+/// Input:
+/// - `pubkeys`: `n` 48-byte compressed G1 points, concatenated.
+/// - `messages`: `n` tight 192-byte uncompressed G2 points, concatenated —
+///   each the output of hashing a message to the curve (e.g. via
+///   [`map_fp2_to_g2`]), not a raw message.
+/// - `signature`: a single 96-byte compressed G2 point, the aggregate of
+///   each signer's `sk_i * message_i`.
+///
+/// Output (32 bytes): a boolean result (`0x00...00` for false,
+/// `0x00...01` for true), following [`bls12_381_pairing`]'s convention.
+/// Checks `e(G1::generator(), signature) == product_i e(pubkey_i,
+/// message_i)`.
+///
+/// # Safety
+///
+/// `pubkeys` must be valid for reads of `pubkeys_len` bytes, `messages` for
+/// reads of `messages_len` bytes, `signature` for reads of
+/// `signature_len` bytes, and `output` for writes of `output_len` bytes,
+/// when non-null.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_aggregate_verify(
+    pubkeys: *const c_uchar,
+    pubkeys_len: c_uint,
+    messages: *const c_uchar,
+    messages_len: c_uint,
+    signature: *const c_uchar,
+    signature_len: c_uint,
+    output: *mut c_uchar,
+    output_len: c_uint,
+) -> c_int {
+    if pubkeys.is_null() || messages.is_null() || signature.is_null() || output.is_null() {
+        return Bls12381Code::NULL_POINTER.raw() as c_int;
+    }
+    if signature_len < 96 || output_len < 32 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    if pubkeys_len % 48 != 0 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+
+    let num_signers = (pubkeys_len / 48) as usize;
+    if messages_len as usize != num_signers * 192 {
+        return Bls12381Code::PUBKEY_MESSAGE_COUNT_MISMATCH.raw() as c_int;
+    }
+
+    let pubkeys_slice = std::slice::from_raw_parts(pubkeys, pubkeys_len as usize);
+    let messages_slice = std::slice::from_raw_parts(messages, messages_len as usize);
+    let signature_slice = std::slice::from_raw_parts(signature, signature_len as usize);
+    let output_slice = std::slice::from_raw_parts_mut(output, output_len as usize);
+
+    // `Ok(None)` is the point at infinity, a valid (if practically useless)
+    // curve point — fold it in as the pairing identity rather than treating
+    // it as an error.
+    let signature_point = match decode_g2_compressed(&signature_slice[0..96]) {
+        Ok(point) => point.unwrap_or(BlsG2Affine::zero()),
+        Err(code) => return code.raw() as c_int,
+    };
+
+    let mut g1_points = Vec::with_capacity(num_signers + 1);
+    let mut g2_points = Vec::with_capacity(num_signers + 1);
+
+    use std::ops::Neg;
+    g1_points.push(BlsG1Affine::generator().neg());
+    g2_points.push(signature_point);
+
+    for i in 0..num_signers {
+        let pubkey = match decode_g1_compressed(&pubkeys_slice[i * 48..(i + 1) * 48]) {
+            Ok(point) => point.unwrap_or(BlsG1Affine::zero()),
+            Err(code) => return code.raw() as c_int,
+        };
+        let message = match decode_g2_uncompressed_tight(&messages_slice[i * 192..(i + 1) * 192]) {
+            Ok(point) => point.unwrap_or(BlsG2Affine::zero()),
+            Err(code) => return code.raw() as c_int,
+        };
+        g1_points.push(pubkey);
+        g2_points.push(message);
+    }
+
+    use ark_bls12_381::Fq12;
+    use ark_ec::pairing::PairingOutput;
+
+    let pairing_result = Bls12_381::multi_pairing(&g1_points, &g2_points);
+    let identity = PairingOutput::<Bls12_381>(Fq12::one());
+
+    output_slice[..32].fill(0);
+    if pairing_result == identity {
+        output_slice[31] = 1;
+    }
+
+    Bls12381Code::SUCCESS.raw() as c_int
+}
+
+/// Output size for `bls12_381_aggregate_verify` (a 32-byte boolean).
+#[no_mangle]
+pub extern "C" fn bls12_381_aggregate_verify_output_size() -> c_uint {
+    32
+}
+
+/// Validate `bls12_381_aggregate_verify`'s input shape: alignment of
+/// `pubkeys_len` to 48 bytes and that `messages_len` holds exactly one
+/// 192-byte hashed message per pubkey. Does not decode the points
+/// themselves — the entry point reports per-point failures directly.
+#[no_mangle]
+pub extern "C" fn bls12_381_aggregate_verify_validate_input(
+    pubkeys_len: c_uint,
+    messages_len: c_uint,
+    signature_len: c_uint,
+) -> c_int {
+    if signature_len < 96 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    if pubkeys_len % 48 != 0 {
+        return Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int;
+    }
+    if messages_len != (pubkeys_len / 48) * 192 {
+        return Bls12381Code::PUBKEY_MESSAGE_COUNT_MISMATCH.raw() as c_int;
+    }
+    Bls12381Code::SUCCESS.raw() as c_int
+}
 
 #[cfg(test)]
 mod tests {
@@ -739,45 +2912,563 @@ mod tests {
     fn test_output_sizes() {
         assert_eq!(bn254_ecmul_output_size(), 64);
         assert_eq!(bn254_ecpairing_output_size(), 32);
+        assert_eq!(bn254_ecadd_output_size(), 64);
     }
 
     #[test]
-    fn test_input_validation() {
+    fn test_ecadd_input_validation() {
         // Test null pointer
         assert_eq!(
-            bn254_ecmul_validate_input(std::ptr::null(), 96),
+            bn254_ecadd_validate_input(std::ptr::null(), 128),
             Bn254Result::InvalidInput as c_int
         );
 
         // Test invalid size
-        let dummy_data = [0u8; 50];
+        let dummy_data = [0u8; 64];
         assert_eq!(
-            bn254_ecmul_validate_input(dummy_data.as_ptr(), 50),
+            bn254_ecadd_validate_input(dummy_data.as_ptr(), 64),
             Bn254Result::InvalidInput as c_int
         );
 
         // Test valid size
-        let dummy_data = [0u8; 96];
+        let dummy_data = [0u8; 128];
         assert_eq!(
-            bn254_ecmul_validate_input(dummy_data.as_ptr(), 96),
+            bn254_ecadd_validate_input(dummy_data.as_ptr(), 128),
             Bn254Result::Success as c_int
         );
     }
 
+    #[test]
+    fn test_ecadd_point_at_infinity() {
+        // Adding two points at infinity should yield the point at infinity
+        let input = [0u8; 128];
+        let mut output = [0xffu8; 64];
+        let result = unsafe { bn254_ecadd(input.as_ptr(), 128, output.as_mut_ptr(), 64) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+        assert_eq!(output, [0u8; 64]);
+    }
+
+    #[test]
+    fn test_input_validation() {
+        // Test null pointer
+        assert_eq!(
+            unsafe { bn254_ecmul_validate_input(std::ptr::null(), 96) },
+            Bn254Code::NULL_POINTER.raw() as c_int
+        );
+
+        // Test invalid size
+        let dummy_data = [0u8; 50];
+        let code = Bn254Code::from(
+            unsafe { bn254_ecmul_validate_input(dummy_data.as_ptr(), 50) } as u16,
+        );
+        assert_eq!(code.group_id(), Bn254Code::GROUP_INPUT_SHAPE);
+
+        // Test valid size, point at infinity
+        let dummy_data = [0u8; 96];
+        assert_eq!(
+            unsafe { bn254_ecmul_validate_input(dummy_data.as_ptr(), 96) },
+            Bn254Code::SUCCESS.raw() as c_int
+        );
+    }
+
     #[test]
     fn test_pairing_input_validation() {
         // Test invalid size (not multiple of 192)
         let dummy_data = [0u8; 100];
         assert_eq!(
-            bn254_ecpairing_validate_input(dummy_data.as_ptr(), 100),
-            Bn254Result::InvalidInput as c_int
+            unsafe { bn254_ecpairing_validate_input(dummy_data.as_ptr(), 100) },
+            Bn254Code::ECPAIRING_LENGTH_NOT_MULTIPLE_OF_192.raw() as c_int
         );
 
-        // Test valid size (multiple of 192)
+        // Test valid size, both points at infinity
         let dummy_data = [0u8; 192];
         assert_eq!(
-            bn254_ecpairing_validate_input(dummy_data.as_ptr(), 192),
+            unsafe { bn254_ecpairing_validate_input(dummy_data.as_ptr(), 192) },
+            Bn254Code::SUCCESS.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_ecmul_validate_input_rejects_out_of_range_coordinate() {
+        // x-coordinate set to a value >= the BN254 base field modulus p.
+        let mut dummy_data = [0u8; 96];
+        dummy_data[0..32].copy_from_slice(&[0xff; 32]);
+        let code =
+            Bn254Code::from(unsafe { bn254_ecmul_validate_input(dummy_data.as_ptr(), 96) } as u16);
+        assert_eq!(code.group_id(), Bn254Code::GROUP_FIELD_RANGE);
+    }
+
+    #[test]
+    fn test_ecmul_validate_input_rejects_point_not_on_curve() {
+        // (1, 1) is in-range but does not satisfy y^2 = x^3 + 3.
+        let mut dummy_data = [0u8; 96];
+        dummy_data[31] = 1;
+        dummy_data[63] = 1;
+        assert_eq!(
+            unsafe { bn254_ecmul_validate_input(dummy_data.as_ptr(), 96) },
+            Bn254Code::G1_NOT_ON_CURVE.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_ecmul_validate_input_accepts_generator() {
+        // The canonical BN254 G1 generator (1, 2).
+        let mut dummy_data = [0u8; 96];
+        dummy_data[31] = 1;
+        dummy_data[63] = 2;
+        assert_eq!(
+            unsafe { bn254_ecmul_validate_input(dummy_data.as_ptr(), 96) },
+            Bn254Code::SUCCESS.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_bn254_code_round_trip() {
+        let code = Bn254Code::G2_NOT_IN_SUBGROUP;
+        let raw = code.raw();
+        assert_eq!(Bn254Code::from(raw), code);
+
+        let mut bytes = [0u8; 2];
+        code.write_to_be_bytes(&mut bytes);
+        assert_eq!(Bn254Code::from_be_bytes(bytes), code);
+    }
+
+    fn modexp_len_header(len: usize) -> [u8; 32] {
+        let mut header = [0u8; 32];
+        header[24..32].copy_from_slice(&(len as u64).to_be_bytes());
+        header
+    }
+
+    fn modexp_input(base: &[u8], exp: &[u8], modulus: &[u8]) -> Vec<u8> {
+        let mut input = Vec::new();
+        input.extend_from_slice(&modexp_len_header(base.len()));
+        input.extend_from_slice(&modexp_len_header(exp.len()));
+        input.extend_from_slice(&modexp_len_header(modulus.len()));
+        input.extend_from_slice(base);
+        input.extend_from_slice(exp);
+        input.extend_from_slice(modulus);
+        input
+    }
+
+    #[test]
+    fn test_modexp_small() {
+        // 3^5 mod 7 == 5
+        let input = modexp_input(&[3], &[5], &[7]);
+        let mut output = [0u8; 1];
+        let result =
+            unsafe { modexp(input.as_ptr(), input.len() as c_uint, output.as_mut_ptr(), 1) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+        assert_eq!(output[0], 5);
+    }
+
+    #[test]
+    fn test_modexp_zero_exponent() {
+        // base^0 mod modulus == 1 mod modulus
+        let input = modexp_input(&[9], &[], &[5]);
+        let mut output = [0u8; 1];
+        let result =
+            unsafe { modexp(input.as_ptr(), input.len() as c_uint, output.as_mut_ptr(), 1) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+        assert_eq!(output[0], 1);
+    }
+
+    #[test]
+    fn test_modexp_zero_base() {
+        // 0^5 mod 7 == 0
+        let input = modexp_input(&[], &[5], &[7]);
+        let mut output = [0xffu8; 1];
+        let result =
+            unsafe { modexp(input.as_ptr(), input.len() as c_uint, output.as_mut_ptr(), 1) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+        assert_eq!(output[0], 0);
+    }
+
+    #[test]
+    fn test_modexp_rejects_length_field_with_nonzero_high_bytes() {
+        // `base_len`'s high 24 bytes (a full 256-bit length per spec) carry
+        // a nonzero byte, meaning an absurd length — must be rejected, not
+        // silently truncated down to the low 8 bytes as if it were 0.
+        let mut input = modexp_input(&[3], &[5], &[7]);
+        input[0] = 0x01;
+        let mut output = [0u8; 1];
+        let result =
+            unsafe { modexp(input.as_ptr(), input.len() as c_uint, output.as_mut_ptr(), 1) };
+        assert_eq!(result, Bn254Result::InvalidInput as c_int);
+    }
+
+    #[test]
+    fn test_modexp_validate_input() {
+        assert_eq!(
+            modexp_validate_input(std::ptr::null(), 96),
+            Bn254Result::InvalidInput as c_int
+        );
+        let dummy_data = [0u8; 50];
+        assert_eq!(
+            modexp_validate_input(dummy_data.as_ptr(), 50),
+            Bn254Result::InvalidInput as c_int
+        );
+        let dummy_data = [0u8; 96];
+        assert_eq!(
+            modexp_validate_input(dummy_data.as_ptr(), 96),
+            Bn254Result::Success as c_int
+        );
+    }
+
+    #[test]
+    fn test_decode_fq_strict_rejects_nonzero_padding() {
+        let mut slab = [0u8; 64];
+        slab[0] = 1; // top padding byte must be zero
+        assert!(decode_fq_strict(&slab).is_none());
+    }
+
+    #[test]
+    fn test_decode_fq_strict_accepts_zero() {
+        let slab = [0u8; 64];
+        assert!(decode_fq_strict(&slab).is_some());
+    }
+
+    #[test]
+    fn test_g2_output_size() {
+        assert_eq!(bls12_381_g2_output_size(), 192);
+    }
+
+    #[test]
+    fn test_g2_add_point_at_infinity() {
+        let input = [0u8; 512];
+        let mut output = [0xffu8; 192];
+        let result =
+            unsafe { bls12_381_g2_add(input.as_ptr(), 512, output.as_mut_ptr(), 192) };
+        assert_eq!(result, Bls12381Result::Success as c_int);
+        assert_eq!(output, [0u8; 192]);
+    }
+
+    #[test]
+    fn test_g1_add_validate_input_rejects_null_and_short_input() {
+        assert_eq!(
+            unsafe { bls12_381_g1_add_validate_input(std::ptr::null(), 256) },
+            Bls12381Code::NULL_POINTER.raw() as c_int
+        );
+        let input = [0u8; 255];
+        assert_eq!(
+            unsafe { bls12_381_g1_add_validate_input(input.as_ptr(), 255) },
+            Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_g1_add_validate_input_rejects_coordinate_out_of_range() {
+        // Top 16 bytes of a 64-byte slab must be zero; set one to violate
+        // the EIP-2537 padding convention `decode_fq_strict` enforces.
+        let mut input = [0u8; 256];
+        input[0] = 1;
+        assert_eq!(
+            unsafe { bls12_381_g1_add_validate_input(input.as_ptr(), 256) },
+            Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_g1_add_validate_input_accepts_infinity_points() {
+        let input = [0u8; 256];
+        assert_eq!(
+            unsafe { bls12_381_g1_add_validate_input(input.as_ptr(), 256) },
+            Bls12381Code::SUCCESS.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_g1_multiexp_validate_input_rejects_length_not_multiple_of_160() {
+        let input = [0u8; 161];
+        assert_eq!(
+            unsafe { bls12_381_g1_multiexp_validate_input(input.as_ptr(), 161) },
+            Bls12381Code::INPUT_LENGTH_MISMATCH.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_pairing_validate_input_accepts_empty_input() {
+        assert_eq!(
+            unsafe { bls12_381_pairing_validate_input(std::ptr::null(), 0) },
+            Bls12381Code::NULL_POINTER.raw() as c_int
+        );
+        let input: [u8; 0] = [];
+        assert_eq!(
+            unsafe { bls12_381_pairing_validate_input(input.as_ptr(), 0) },
+            Bls12381Code::SUCCESS.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_map_fp_to_g1_validate_input_rejects_coordinate_out_of_range() {
+        let mut input = [0u8; 64];
+        input[0] = 1;
+        assert_eq!(
+            unsafe { map_fp_to_g1_validate_input(input.as_ptr(), 64) },
+            Bls12381Code::COORDINATE_OUT_OF_RANGE.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_map_fp2_to_g2_validate_input_accepts_zero() {
+        let input = [0u8; 128];
+        assert_eq!(
+            unsafe { map_fp2_to_g2_validate_input(input.as_ptr(), 128) },
+            Bls12381Code::SUCCESS.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_ecrecover_output_size() {
+        assert_eq!(ecrecover_output_size(), 32);
+    }
+
+    #[test]
+    fn test_ecrecover_validate_input() {
+        assert_eq!(
+            ecrecover_validate_input(std::ptr::null(), 128),
+            Bn254Result::InvalidInput as c_int
+        );
+        let dummy_data = [0u8; 100];
+        assert_eq!(
+            ecrecover_validate_input(dummy_data.as_ptr(), 100),
+            Bn254Result::InvalidInput as c_int
+        );
+        let dummy_data = [0u8; 128];
+        assert_eq!(
+            ecrecover_validate_input(dummy_data.as_ptr(), 128),
             Bn254Result::Success as c_int
         );
     }
+
+    #[test]
+    fn test_ecrecover_invalid_v_returns_zero_address() {
+        // v = 0 is not 27 or 28, so recovery must fail closed with an
+        // all-zero output rather than an error code.
+        let mut input = [0u8; 128];
+        input[63] = 0;
+        let mut output = [0xffu8; 32];
+        let result =
+            unsafe { ecrecover(input.as_ptr(), 128, output.as_mut_ptr(), 32) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+        assert_eq!(output, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_bn254_ecpairing_gas() {
+        assert_eq!(bn254_ecpairing_gas(0), BN254_ECPAIRING_BASE_GAS as c_uint);
+        assert_eq!(
+            bn254_ecpairing_gas(192),
+            (BN254_ECPAIRING_BASE_GAS + BN254_ECPAIRING_PER_PAIR_GAS) as c_uint
+        );
+    }
+
+    #[test]
+    fn test_bls12_381_multiexp_gas_discount_clamps() {
+        // k=1 has no discount, so gas is exactly the base cost.
+        assert_eq!(
+            bls12_381_g1_multiexp_gas(160),
+            BLS12_381_G1_MUL_BASE_GAS as c_uint
+        );
+        // k beyond the 128-entry table clamps to the final discount.
+        let k = 200;
+        let expected =
+            (k as u64 * BLS12_381_G1_MUL_BASE_GAS * MULTIEXP_DISCOUNT_TABLE[127] / 1000) as c_uint;
+        assert_eq!(bls12_381_g1_multiexp_gas((k * 160) as c_uint), expected);
+    }
+
+    #[test]
+    fn test_map_to_curve_output_sizes() {
+        assert_eq!(map_fp_to_g1_output_size(), 128);
+        assert_eq!(map_fp2_to_g2_output_size(), 192);
+    }
+
+    #[test]
+    fn test_decode_fq_strict_rejects_out_of_range() {
+        // The base field modulus p is slightly less than 2^381; an all-0xff
+        // 48-byte value is far outside the field and must be rejected rather
+        // than silently reduced.
+        let mut slab = [0u8; 64];
+        slab[16..64].fill(0xff);
+        assert!(decode_fq_strict(&slab).is_none());
+    }
+
+    #[test]
+    fn test_g1_compress_decompress_round_trip() {
+        let generator = BlsG1Affine::generator();
+        let mut uncompressed = [0u8; 96];
+        encode_g1_uncompressed(Some(generator), &mut uncompressed);
+
+        let mut compressed = [0xffu8; 48];
+        let result = unsafe {
+            bls12_381_g1_compress(
+                uncompressed.as_ptr(),
+                96,
+                compressed.as_mut_ptr(),
+                48,
+            )
+        };
+        assert_eq!(result, Bls12381Code::SUCCESS.raw() as c_int);
+        assert_eq!(compressed[0] & BLS_COMPRESSION_FLAG, BLS_COMPRESSION_FLAG);
+        assert_eq!(compressed[0] & BLS_INFINITY_FLAG, 0);
+
+        let mut round_tripped = [0u8; 96];
+        let result = unsafe {
+            bls12_381_g1_decompress(
+                compressed.as_ptr(),
+                48,
+                round_tripped.as_mut_ptr(),
+                96,
+            )
+        };
+        assert_eq!(result, Bls12381Code::SUCCESS.raw() as c_int);
+        assert_eq!(round_tripped, uncompressed);
+    }
+
+    #[test]
+    fn test_g1_compress_infinity() {
+        let uncompressed = [0u8; 96];
+        let mut compressed = [0xffu8; 48];
+        let result = unsafe {
+            bls12_381_g1_compress(
+                uncompressed.as_ptr(),
+                96,
+                compressed.as_mut_ptr(),
+                48,
+            )
+        };
+        assert_eq!(result, Bls12381Code::SUCCESS.raw() as c_int);
+        assert_eq!(compressed[0], BLS_COMPRESSION_FLAG | BLS_INFINITY_FLAG);
+        assert_eq!(&compressed[1..], &[0u8; 47][..]);
+    }
+
+    #[test]
+    fn test_g1_decompress_rejects_missing_compression_flag() {
+        let input = [0u8; 48];
+        let mut output = [0u8; 96];
+        let result = unsafe {
+            bls12_381_g1_decompress(input.as_ptr(), 48, output.as_mut_ptr(), 96)
+        };
+        assert_eq!(result, Bls12381Code::BAD_COMPRESSION_FLAG.raw() as c_int);
+    }
+
+    #[test]
+    fn test_g2_compress_decompress_round_trip() {
+        let generator = BlsG2Affine::generator();
+        let mut uncompressed = [0u8; 192];
+        encode_g2_uncompressed(Some(generator), &mut uncompressed);
+
+        let mut compressed = [0xffu8; 96];
+        let result = unsafe {
+            bls12_381_g2_compress(
+                uncompressed.as_ptr(),
+                192,
+                compressed.as_mut_ptr(),
+                96,
+            )
+        };
+        assert_eq!(result, Bls12381Code::SUCCESS.raw() as c_int);
+
+        let mut round_tripped = [0u8; 192];
+        let result = unsafe {
+            bls12_381_g2_decompress(
+                compressed.as_ptr(),
+                96,
+                round_tripped.as_mut_ptr(),
+                192,
+            )
+        };
+        assert_eq!(result, Bls12381Code::SUCCESS.raw() as c_int);
+        assert_eq!(round_tripped, uncompressed);
+    }
+
+    #[test]
+    fn test_aggregate_verify_output_size() {
+        assert_eq!(bls12_381_aggregate_verify_output_size(), 32);
+    }
+
+    #[test]
+    fn test_aggregate_verify_validate_input_mismatch() {
+        // 1 pubkey (48 bytes) but 0 messages: count mismatch.
+        assert_eq!(
+            bls12_381_aggregate_verify_validate_input(48, 0, 96),
+            Bls12381Code::PUBKEY_MESSAGE_COUNT_MISMATCH.raw() as c_int
+        );
+
+        assert_eq!(
+            bls12_381_aggregate_verify_validate_input(48, 192, 96),
+            Bls12381Code::SUCCESS.raw() as c_int
+        );
+    }
+
+    #[test]
+    fn test_ecmsm_output_size() {
+        assert_eq!(bn254_ecmsm_output_size(), 64);
+    }
+
+    #[test]
+    fn test_ecmsm_validate_input_rejects_misaligned_length() {
+        let code = Bn254Code::from(unsafe { bn254_ecmsm_validate_input(std::ptr::null(), 0) } as u16);
+        assert_eq!(code, Bn254Code::NULL_POINTER);
+
+        let dummy = [0u8; 100];
+        let code =
+            Bn254Code::from(unsafe { bn254_ecmsm_validate_input(dummy.as_ptr(), 100) } as u16);
+        assert_eq!(code.group_id(), Bn254Code::GROUP_INPUT_SHAPE);
+    }
+
+    #[test]
+    fn test_ecmsm_empty_input_returns_infinity() {
+        // A non-null, zero-length input: matches `bn254_ecmul`/
+        // `bn254_ecpairing`'s convention of unconditionally rejecting a null
+        // pointer regardless of length, so an empty call site must pass a
+        // non-null pointer to a zero-length slice.
+        let input: [u8; 0] = [];
+        let mut output = [0xffu8; 64];
+        let result = unsafe { bn254_ecmsm(input.as_ptr(), 0, output.as_mut_ptr(), 64) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+        assert_eq!(output, [0u8; 64]);
+    }
+
+    #[test]
+    fn test_ecmsm_matches_sum_of_individual_scalar_muls() {
+        use ark_bn254::Fr;
+
+        fn be32(v: u64) -> [u8; 32] {
+            let mut out = [0u8; 32];
+            out[24..32].copy_from_slice(&v.to_be_bytes());
+            out
+        }
+
+        fn write_point(input: &mut [u8], offset: usize, point: G1Affine) {
+            let x_bytes = point.x().unwrap().into_bigint().to_bytes_be();
+            let y_bytes = point.y().unwrap().into_bigint().to_bytes_be();
+            input[offset..offset + 32].fill(0);
+            input[offset + 32..offset + 64].fill(0);
+            input[offset + 32 - x_bytes.len()..offset + 32].copy_from_slice(&x_bytes);
+            input[offset + 64 - y_bytes.len()..offset + 64].copy_from_slice(&y_bytes);
+        }
+
+        // Two terms: the generator (1, 2) scaled by 3, and its double scaled
+        // by 5.
+        let generator = G1Affine::new_unchecked(ark_bn254::Fq::from(1u64), ark_bn254::Fq::from(2u64));
+        let doubled = (generator + generator).into_affine();
+
+        let scalar_a = Fr::from(3u64);
+        let scalar_b = Fr::from(5u64);
+        let expected = (generator * scalar_a + doubled * scalar_b).into_affine();
+
+        let mut input = [0u8; 192];
+        write_point(&mut input, 0, generator);
+        input[64..96].copy_from_slice(&be32(3));
+        write_point(&mut input, 96, doubled);
+        input[160..192].copy_from_slice(&be32(5));
+
+        let mut output = [0u8; 64];
+        let result = unsafe { bn254_ecmsm(input.as_ptr(), 192, output.as_mut_ptr(), 64) };
+        assert_eq!(result, Bn254Result::Success as c_int);
+
+        let mut expected_output = [0u8; 64];
+        write_point(&mut expected_output, 0, expected);
+        assert_eq!(output, expected_output);
+    }
 }
@@ -0,0 +1,219 @@
+use revm::{
+    db::CacheDB,
+    primitives::{Address, B256, U256},
+    DatabaseRef,
+};
+use std::collections::{BTreeMap, HashSet};
+
+/// A plain, deterministic snapshot of one account, independent of any
+/// particular `CacheDB` internals, so two snapshots can be diffed and
+/// printed without pulling in the full `AccountInfo`/storage-map types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountPod {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code_hash: B256,
+    pub code: Vec<u8>,
+    pub storage: BTreeMap<U256, U256>,
+}
+
+impl AccountPod {
+    fn from_db<ExtDB: DatabaseRef>(db: &CacheDB<ExtDB>, address: Address) -> Option<Self> {
+        let account = db.cache.accounts.get(&address)?;
+
+        Some(Self {
+            balance: account.info.balance,
+            nonce: account.info.nonce,
+            code_hash: account.info.code_hash,
+            code: account
+                .info
+                .code
+                .clone()
+                .map(|c| c.bytes().to_vec())
+                .unwrap_or_default(),
+            storage: account.storage.iter().map(|(k, v)| (*k, *v)).collect(),
+        })
+    }
+}
+
+/// The per-account delta produced by [`diff_state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountDiff {
+    Added(AccountPod),
+    Deleted(AccountPod),
+    Changed {
+        balance: (U256, U256),
+        nonce: (u64, u64),
+        code_hash: (B256, B256),
+        /// Modified storage slots, old value -> new value, including slots
+        /// that were set to zero.
+        storage: BTreeMap<U256, (U256, U256)>,
+    },
+}
+
+/// Compare two states (e.g. a pre- and post-execution `CacheDB`) and return a
+/// per-account delta for every address whose account differs between them
+/// (an address present in both with an identical account gets no entry),
+/// turning the ad-hoc `println!` inspection of state into a structured,
+/// testable diff.
+pub fn diff_state<ExtDB: DatabaseRef>(
+    before: &CacheDB<ExtDB>,
+    after: &CacheDB<ExtDB>,
+) -> BTreeMap<Address, AccountDiff> {
+    let mut addresses: HashSet<Address> = HashSet::new();
+    addresses.extend(before.cache.accounts.keys().copied());
+    addresses.extend(after.cache.accounts.keys().copied());
+
+    let mut diffs = BTreeMap::new();
+
+    for address in addresses {
+        let before_pod = AccountPod::from_db(before, address);
+        let after_pod = AccountPod::from_db(after, address);
+
+        let diff = match (before_pod, after_pod) {
+            (None, Some(after_pod)) => AccountDiff::Added(after_pod),
+            (Some(before_pod), None) => AccountDiff::Deleted(before_pod),
+            (Some(before_pod), Some(after_pod)) => {
+                if before_pod == after_pod {
+                    continue;
+                }
+
+                let mut storage = BTreeMap::new();
+                let mut keys: HashSet<U256> = HashSet::new();
+                keys.extend(before_pod.storage.keys().copied());
+                keys.extend(after_pod.storage.keys().copied());
+                for key in keys {
+                    let old = before_pod.storage.get(&key).copied().unwrap_or_default();
+                    let new = after_pod.storage.get(&key).copied().unwrap_or_default();
+                    if old != new {
+                        storage.insert(key, (old, new));
+                    }
+                }
+
+                AccountDiff::Changed {
+                    balance: (before_pod.balance, after_pod.balance),
+                    nonce: (before_pod.nonce, after_pod.nonce),
+                    code_hash: (before_pod.code_hash, after_pod.code_hash),
+                    storage,
+                }
+            }
+            (None, None) => continue,
+        };
+
+        diffs.insert(address, diff);
+    }
+
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::{CacheDB, EmptyDB};
+    use revm::primitives::AccountInfo;
+
+    fn new_db() -> CacheDB<EmptyDB> {
+        CacheDB::new(EmptyDB::default())
+    }
+
+    #[test]
+    fn test_diff_state_reports_added_deleted_and_changed_accounts() {
+        let added_addr = Address::from([0x01; 20]);
+        let deleted_addr = Address::from([0x02; 20]);
+        let changed_addr = Address::from([0x03; 20]);
+        let unchanged_addr = Address::from([0x04; 20]);
+
+        let mut before = new_db();
+        before.insert_account_info(
+            deleted_addr,
+            AccountInfo {
+                balance: U256::from(10),
+                ..Default::default()
+            },
+        );
+        before.insert_account_info(
+            changed_addr,
+            AccountInfo {
+                balance: U256::from(100),
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        before
+            .cache
+            .accounts
+            .get_mut(&changed_addr)
+            .unwrap()
+            .storage
+            .insert(U256::from(1), U256::from(111));
+        before.insert_account_info(
+            unchanged_addr,
+            AccountInfo {
+                balance: U256::from(5),
+                ..Default::default()
+            },
+        );
+
+        let mut after = new_db();
+        after.insert_account_info(
+            added_addr,
+            AccountInfo {
+                balance: U256::from(20),
+                ..Default::default()
+            },
+        );
+        after.insert_account_info(
+            changed_addr,
+            AccountInfo {
+                balance: U256::from(200),
+                nonce: 2,
+                ..Default::default()
+            },
+        );
+        after
+            .cache
+            .accounts
+            .get_mut(&changed_addr)
+            .unwrap()
+            .storage
+            .insert(U256::from(1), U256::from(222));
+        after.insert_account_info(
+            unchanged_addr,
+            AccountInfo {
+                balance: U256::from(5),
+                ..Default::default()
+            },
+        );
+
+        let diffs = diff_state(&before, &after);
+
+        assert!(!diffs.contains_key(&unchanged_addr));
+
+        match diffs.get(&added_addr).unwrap() {
+            AccountDiff::Added(pod) => assert_eq!(pod.balance, U256::from(20)),
+            other => panic!("expected Added, got {other:?}"),
+        }
+
+        match diffs.get(&deleted_addr).unwrap() {
+            AccountDiff::Deleted(pod) => assert_eq!(pod.balance, U256::from(10)),
+            other => panic!("expected Deleted, got {other:?}"),
+        }
+
+        match diffs.get(&changed_addr).unwrap() {
+            AccountDiff::Changed {
+                balance,
+                nonce,
+                storage,
+                ..
+            } => {
+                assert_eq!(*balance, (U256::from(100), U256::from(200)));
+                assert_eq!(*nonce, (1, 2));
+                assert_eq!(
+                    storage.get(&U256::from(1)),
+                    Some(&(U256::from(111), U256::from(222)))
+                );
+            }
+            other => panic!("expected Changed, got {other:?}"),
+        }
+    }
+}
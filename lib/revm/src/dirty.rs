@@ -0,0 +1,170 @@
+use revm::{
+    db::CacheDB,
+    primitives::{AccountInfo, Address, U256},
+    DatabaseRef,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Clean/dirty marker for an account or storage slot, in the spirit of the
+/// classic `Filth` tag: `Clean` means the backing DB already reflects this
+/// value, `Dirty` means it was changed locally and still needs to be
+/// written back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filth {
+    Clean,
+    Dirty,
+}
+
+/// A `CacheDB` wrapper that tags every account and storage slot mutation as
+/// dirty, so `commit()` can write back only what actually changed instead of
+/// flushing the whole cache, and the checkpoint/journal layer can target
+/// exactly the dirty set on revert.
+pub struct DirtyTrackingDb<ExtDB> {
+    db: CacheDB<ExtDB>,
+    account_filth: HashMap<Address, Filth>,
+    storage_filth: HashMap<Address, HashSet<U256>>,
+}
+
+impl<ExtDB: DatabaseRef> DirtyTrackingDb<ExtDB> {
+    pub fn new(db: CacheDB<ExtDB>) -> Self {
+        Self {
+            db,
+            account_filth: HashMap::new(),
+            storage_filth: HashMap::new(),
+        }
+    }
+
+    /// Set an account's info and mark it dirty.
+    pub fn set_account_info(&mut self, address: Address, info: AccountInfo) {
+        self.db.insert_account_info(address, info);
+        self.account_filth.insert(address, Filth::Dirty);
+    }
+
+    /// Set a storage slot and mark it dirty.
+    ///
+    /// If `address` isn't loaded into the cache yet, this also marks the
+    /// account itself dirty, since the write implicitly creates it — a
+    /// silently-fabricated, untracked account would otherwise be invisible
+    /// to `dirty_accounts()` while still showing up in `commit()`'s touched
+    /// set via `storage_filth`.
+    pub fn set_storage(&mut self, address: Address, key: U256, value: U256) {
+        if !self.db.cache.accounts.contains_key(&address) {
+            self.account_filth.insert(address, Filth::Dirty);
+        }
+        self.db
+            .cache
+            .accounts
+            .entry(address)
+            .or_default()
+            .storage
+            .insert(key, value);
+        self.storage_filth.entry(address).or_default().insert(key);
+    }
+
+    /// Addresses with a dirty account entry since the last `commit()`.
+    pub fn dirty_accounts(&self) -> impl Iterator<Item = Address> + '_ {
+        self.account_filth
+            .iter()
+            .filter(|(_, filth)| **filth == Filth::Dirty)
+            .map(|(address, _)| *address)
+    }
+
+    /// Storage keys dirtied for `address` since the last `commit()`.
+    pub fn dirty_storage(&self, address: Address) -> impl Iterator<Item = U256> + '_ {
+        self.storage_filth
+            .get(&address)
+            .into_iter()
+            .flat_map(|keys| keys.iter().copied())
+    }
+
+    /// Write back only the dirty accounts/slots via `writer`, then reset
+    /// them to clean. `writer` receives each dirty address with its current
+    /// info and the set of dirty (key, value) pairs for that address.
+    pub fn commit(
+        &mut self,
+        mut writer: impl FnMut(Address, &AccountInfo, &[(U256, U256)]),
+    ) {
+        let dirty_addresses: Vec<Address> = self.dirty_accounts().collect();
+        let mut touched: HashSet<Address> = dirty_addresses.iter().copied().collect();
+        touched.extend(self.storage_filth.keys().copied());
+
+        for address in touched {
+            let Some(account) = self.db.cache.accounts.get(&address) else {
+                continue;
+            };
+
+            let dirty_slots: Vec<(U256, U256)> = self
+                .storage_filth
+                .get(&address)
+                .map(|keys| {
+                    keys.iter()
+                        .filter_map(|key| account.storage.get(key).map(|value| (*key, *value)))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            writer(address, &account.info, &dirty_slots);
+        }
+
+        self.account_filth.clear();
+        self.storage_filth.clear();
+    }
+
+    pub fn db(&self) -> &CacheDB<ExtDB> {
+        &self.db
+    }
+
+    pub fn db_mut(&mut self) -> &mut CacheDB<ExtDB> {
+        &mut self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::{CacheDB, EmptyDB};
+
+    fn new_tracker() -> DirtyTrackingDb<EmptyDB> {
+        DirtyTrackingDb::new(CacheDB::new(EmptyDB::default()))
+    }
+
+    #[test]
+    fn test_commit_skips_clean_accounts_and_writes_back_only_dirty_ones() {
+        let mut tracker = new_tracker();
+        let dirty_addr = Address::from([0x11; 20]);
+        let clean_addr = Address::from([0x22; 20]);
+
+        tracker.set_account_info(dirty_addr, AccountInfo::default());
+        tracker.set_storage(dirty_addr, U256::from(1), U256::from(42));
+
+        // `clean_addr` is populated directly (bypassing the dirty-tracking
+        // setters), so it must never appear in `commit`'s written-back set.
+        tracker
+            .db_mut()
+            .insert_account_info(clean_addr, AccountInfo::default());
+
+        let mut written: Vec<Address> = Vec::new();
+        tracker.commit(|address, _info, slots| {
+            written.push(address);
+            assert_eq!(slots, &[(U256::from(1), U256::from(42))]);
+        });
+
+        assert_eq!(written, vec![dirty_addr]);
+        assert_eq!(tracker.dirty_accounts().count(), 0);
+        assert_eq!(tracker.dirty_storage(dirty_addr).count(), 0);
+    }
+
+    #[test]
+    fn test_commit_is_a_no_op_on_a_second_call_with_no_new_mutations() {
+        let mut tracker = new_tracker();
+        let address = Address::from([0x33; 20]);
+        tracker.set_account_info(address, AccountInfo::default());
+
+        tracker.commit(|_, _, _| {});
+
+        let mut second_call_count = 0;
+        tracker.commit(|_, _, _| second_call_count += 1);
+
+        assert_eq!(second_call_count, 0);
+    }
+}
@@ -0,0 +1,239 @@
+use revm::{
+    db::CacheDB,
+    primitives::{Address, AccountInfo, U256},
+    DatabaseRef,
+};
+
+/// One undo step recorded while a checkpoint frame is open.
+///
+/// Each variant carries the value that was in place *before* the mutation it
+/// guards, so replaying entries in reverse order restores state exactly.
+enum JournalEntry {
+    AccountChanged {
+        address: Address,
+        /// `None` means the account did not exist before the mutation.
+        prior: Option<AccountInfo>,
+    },
+    StorageChanged {
+        address: Address,
+        key: U256,
+        prior: U256,
+    },
+}
+
+/// A checkpoint/revert journal layered over a `revm` [`CacheDB`].
+///
+/// Mutations made through [`JournaledState::set_account_info`] and
+/// [`JournaledState::set_storage`] are recorded into the topmost checkpoint
+/// frame before being applied. `revert_to_checkpoint` pops that frame and
+/// replays its entries in reverse to undo exactly what happened since the
+/// matching `checkpoint()` call; `commit_checkpoint` instead folds the frame
+/// into its parent so nested checkpoints compose.
+pub struct JournaledState<ExtDB> {
+    db: CacheDB<ExtDB>,
+    frames: Vec<Vec<JournalEntry>>,
+}
+
+impl<ExtDB: DatabaseRef> JournaledState<ExtDB> {
+    pub fn new(db: CacheDB<ExtDB>) -> Self {
+        Self {
+            db,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Push a new journal frame. Mutations after this call are recorded into
+    /// it, to be undone by the matching `revert_to_checkpoint` or kept by the
+    /// matching `commit_checkpoint`.
+    pub fn checkpoint(&mut self) {
+        self.frames.push(Vec::new());
+    }
+
+    fn record(&mut self, entry: JournalEntry) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.push(entry);
+        }
+    }
+
+    /// Pop the topmost frame and undo every mutation it recorded, in reverse
+    /// order, restoring state to exactly how it was at the matching
+    /// `checkpoint()` call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no open checkpoint to revert.
+    pub fn revert_to_checkpoint(&mut self) {
+        let frame = self
+            .frames
+            .pop()
+            .expect("revert_to_checkpoint called without a matching checkpoint");
+
+        for entry in frame.into_iter().rev() {
+            match entry {
+                JournalEntry::AccountChanged { address, prior } => match prior {
+                    Some(info) => {
+                        self.db.insert_account_info(address, info);
+                    }
+                    None => {
+                        self.db.cache.accounts.remove(&address);
+                    }
+                },
+                JournalEntry::StorageChanged { address, key, prior } => {
+                    if let Some(account) = self.db.cache.accounts.get_mut(&address) {
+                        account.storage.insert(key, prior);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pop the topmost frame and merge its entries into the parent frame (or
+    /// discard them if this was the outermost checkpoint), keeping the
+    /// mutations applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no open checkpoint to commit.
+    pub fn commit_checkpoint(&mut self) {
+        let frame = self
+            .frames
+            .pop()
+            .expect("commit_checkpoint called without a matching checkpoint");
+
+        if let Some(parent) = self.frames.last_mut() {
+            parent.extend(frame);
+        }
+    }
+
+    /// Set an account's info, journaling whatever was there before (or the
+    /// fact that nothing was).
+    pub fn set_account_info(&mut self, address: Address, info: AccountInfo) {
+        let prior = self
+            .db
+            .cache
+            .accounts
+            .get(&address)
+            .map(|account| account.info.clone());
+        self.record(JournalEntry::AccountChanged { address, prior });
+        self.db.insert_account_info(address, info);
+    }
+
+    /// Set a single storage slot, journaling its prior value.
+    ///
+    /// If `address` isn't loaded into the cache yet, this also journals the
+    /// implicit account creation (`AccountChanged { prior: None }`) before
+    /// the storage write, so reverting removes the phantom account entirely
+    /// instead of leaving a zeroed account behind.
+    pub fn set_storage(&mut self, address: Address, key: U256, value: U256) {
+        if !self.db.cache.accounts.contains_key(&address) {
+            self.record(JournalEntry::AccountChanged {
+                address,
+                prior: None,
+            });
+        }
+        let prior = self
+            .db
+            .cache
+            .accounts
+            .get(&address)
+            .and_then(|account| account.storage.get(&key).copied())
+            .unwrap_or_default();
+        self.record(JournalEntry::StorageChanged {
+            address,
+            key,
+            prior,
+        });
+        self.db
+            .cache
+            .accounts
+            .entry(address)
+            .or_default()
+            .storage
+            .insert(key, value);
+    }
+
+    /// Current checkpoint depth.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn db(&self) -> &CacheDB<ExtDB> {
+        &self.db
+    }
+
+    pub fn db_mut(&mut self) -> &mut CacheDB<ExtDB> {
+        &mut self.db
+    }
+
+    pub fn into_db(self) -> CacheDB<ExtDB> {
+        self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::EmptyDB;
+
+    fn new_journal() -> JournaledState<EmptyDB> {
+        JournaledState::new(CacheDB::new(EmptyDB::default()))
+    }
+
+    #[test]
+    fn test_revert_to_checkpoint_undoes_storage_write() {
+        let mut journal = new_journal();
+        let address = Address::from([0x11; 20]);
+        let key = U256::from(1);
+
+        journal.set_storage(address, key, U256::from(100));
+        journal.checkpoint();
+        journal.set_storage(address, key, U256::from(200));
+        assert_eq!(
+            journal.db().cache.accounts.get(&address).unwrap().storage[&key],
+            U256::from(200)
+        );
+
+        journal.revert_to_checkpoint();
+
+        assert_eq!(
+            journal.db().cache.accounts.get(&address).unwrap().storage[&key],
+            U256::from(100)
+        );
+        assert_eq!(journal.depth(), 0);
+    }
+
+    #[test]
+    fn test_revert_to_checkpoint_removes_phantom_account() {
+        let mut journal = new_journal();
+        let address = Address::from([0x22; 20]);
+
+        journal.checkpoint();
+        journal.set_storage(address, U256::from(1), U256::from(42));
+        assert!(journal.db().cache.accounts.contains_key(&address));
+
+        journal.revert_to_checkpoint();
+
+        assert!(!journal.db().cache.accounts.contains_key(&address));
+    }
+
+    #[test]
+    fn test_commit_checkpoint_keeps_mutation_and_merges_into_parent() {
+        let mut journal = new_journal();
+        let address = Address::from([0x33; 20]);
+
+        journal.checkpoint();
+        journal.checkpoint();
+        journal.set_account_info(address, AccountInfo::default());
+        journal.commit_checkpoint();
+        assert_eq!(journal.depth(), 1);
+        assert!(journal.db().cache.accounts.contains_key(&address));
+
+        // The merged entry is still undone by the outer checkpoint's revert,
+        // proving commit folded it into the parent frame rather than
+        // discarding it.
+        journal.revert_to_checkpoint();
+
+        assert!(!journal.db().cache.accounts.contains_key(&address));
+        assert_eq!(journal.depth(), 0);
+    }
+}
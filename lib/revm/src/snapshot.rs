@@ -0,0 +1,290 @@
+use revm::{
+    db::CacheDB,
+    primitives::{AccountInfo, Address, Bytecode, Bytes, B256, U256},
+    DatabaseRef,
+};
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+
+/// Code marker bytes in the fat-RLP account encoding, mirroring the classic
+/// "fat RLP" account snapshot format: a code-less account, an inline first
+/// occurrence of a code blob, or a back-reference to a code hash already
+/// emitted earlier in the stream.
+const CODE_EMPTY: u8 = 0;
+const CODE_INLINE: u8 = 1;
+const CODE_HASH: u8 = 2;
+
+/// Generous but finite caps on snapshot header-declared lengths/counts. A
+/// truncated or corrupted snapshot can carry an arbitrary `u64` in any of
+/// these header fields; without a cap, `import_snapshot` would size a
+/// `Vec` allocation (or a loop bound) directly from that untrusted value,
+/// and a multi-gigabyte-to-exabyte allocation attempt aborts the process
+/// instead of returning an error. Real snapshots are many orders of
+/// magnitude below these.
+const MAX_CODE_LEN: u64 = 1 << 30; // 1 GiB
+const MAX_ACCOUNTS: u64 = 100_000_000;
+const MAX_SLOTS_PER_ACCOUNT: u64 = 100_000_000;
+
+fn write_u64(writer: &mut impl Write, value: u64) -> io::Result<()> {
+    writer.write_all(&value.to_be_bytes())
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_be_bytes(buf))
+}
+
+fn write_u256(writer: &mut impl Write, value: U256) -> io::Result<()> {
+    writer.write_all(&value.to_be_bytes::<32>())
+}
+
+fn read_u256(reader: &mut impl Read) -> io::Result<U256> {
+    let mut buf = [0u8; 32];
+    reader.read_exact(&mut buf)?;
+    Ok(U256::from_be_bytes(buf))
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    write_u64(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let len = read_u64(reader)?;
+    if len > MAX_CODE_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("snapshot code blob length {len} exceeds the {MAX_CODE_LEN}-byte cap"),
+        ));
+    }
+    let mut buf = vec![0u8; len as usize];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Serialize every account in `db` to `writer` using a fat-RLP-style layout:
+/// for each account, its nonce, balance, a code marker
+/// (empty/inline-first-occurrence/hash-reference), and its storage as a list
+/// of (key, value) pairs. Bytecode shared across accounts (same code hash)
+/// is written only once; subsequent accounts reference it by hash.
+pub fn export_snapshot<ExtDB: DatabaseRef>(
+    db: &CacheDB<ExtDB>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let mut seen_code: HashSet<B256> = HashSet::new();
+
+    write_u64(writer, db.cache.accounts.len() as u64)?;
+
+    for (address, account) in db.cache.accounts.iter() {
+        writer.write_all(address.as_slice())?;
+        write_u64(writer, account.info.nonce)?;
+        write_u256(writer, account.info.balance)?;
+
+        let code_hash = account.info.code_hash;
+        if code_hash == revm::primitives::KECCAK_EMPTY {
+            writer.write_all(&[CODE_EMPTY])?;
+        } else if let Some(code) = account.info.code.as_ref().filter(|_| !seen_code.contains(&code_hash)) {
+            // Only the first account whose bytecode is actually *loaded*
+            // gets to inline it; an account with `code: None` (e.g. one
+            // only ever touched via `basic()`) has no bytes to inline and
+            // must fall through to the hash-reference branch instead, or
+            // we'd silently re-encode it as an empty blob under the wrong
+            // hash.
+            seen_code.insert(code_hash);
+            writer.write_all(&[CODE_INLINE])?;
+            write_bytes(writer, &code.clone().bytes())?;
+        } else {
+            writer.write_all(&[CODE_HASH])?;
+            writer.write_all(code_hash.as_slice())?;
+        }
+
+        write_u64(writer, account.storage.len() as u64)?;
+        for (key, value) in account.storage.iter() {
+            write_u256(writer, *key)?;
+            write_u256(writer, *value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Restore an account set previously written by [`export_snapshot`] into
+/// `db`, reconstructing `Bytecode` from inlined bytes and re-linking `Hash`
+/// references against the accounts already seen in this stream.
+pub fn import_snapshot<ExtDB: DatabaseRef>(
+    db: &mut CacheDB<ExtDB>,
+    reader: &mut impl Read,
+) -> io::Result<()> {
+    let mut code_by_hash: std::collections::HashMap<B256, Bytecode> = std::collections::HashMap::new();
+
+    let num_accounts = read_u64(reader)?;
+    if num_accounts > MAX_ACCOUNTS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("snapshot account count {num_accounts} exceeds the {MAX_ACCOUNTS}-account cap"),
+        ));
+    }
+    for _ in 0..num_accounts {
+        let mut addr_buf = [0u8; 20];
+        reader.read_exact(&mut addr_buf)?;
+        let address = Address::from(addr_buf);
+
+        let nonce = read_u64(reader)?;
+        let balance = read_u256(reader)?;
+
+        let mut marker = [0u8; 1];
+        reader.read_exact(&mut marker)?;
+
+        let (code, code_hash) = match marker[0] {
+            CODE_EMPTY => (None, revm::primitives::KECCAK_EMPTY),
+            CODE_INLINE => {
+                let raw = read_bytes(reader)?;
+                let bytecode = Bytecode::new_raw(Bytes::from(raw.clone()));
+                let hash = revm::primitives::keccak256(&raw);
+                code_by_hash.insert(hash, bytecode.clone());
+                (Some(bytecode), hash)
+            }
+            CODE_HASH => {
+                let mut hash_buf = [0u8; 32];
+                reader.read_exact(&mut hash_buf)?;
+                let hash = B256::from(hash_buf);
+                let bytecode = code_by_hash.get(&hash).cloned();
+                (bytecode, hash)
+            }
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown code marker byte {other}"),
+                ))
+            }
+        };
+
+        let info = AccountInfo {
+            balance,
+            nonce,
+            code_hash,
+            code,
+        };
+        db.insert_account_info(address, info);
+
+        let num_slots = read_u64(reader)?;
+        if num_slots > MAX_SLOTS_PER_ACCOUNT {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "snapshot storage slot count {num_slots} exceeds the {MAX_SLOTS_PER_ACCOUNT}-slot cap"
+                ),
+            ));
+        }
+        for _ in 0..num_slots {
+            let key = read_u256(reader)?;
+            let value = read_u256(reader)?;
+            db.cache
+                .accounts
+                .entry(address)
+                .or_default()
+                .storage
+                .insert(key, value);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::EmptyDB;
+
+    fn new_db() -> CacheDB<EmptyDB> {
+        CacheDB::new(EmptyDB::default())
+    }
+
+    #[test]
+    fn test_export_import_round_trip_with_shared_code() {
+        let mut db = new_db();
+
+        let shared_code = vec![0x60, 0x01, 0x60, 0x02, 0x01];
+        let shared_bytecode = Bytecode::new_raw(Bytes::from(shared_code.clone()));
+        let shared_hash = revm::primitives::keccak256(&shared_code);
+
+        let addr_a = Address::from([0xaa; 20]);
+        let addr_b = Address::from([0xbb; 20]);
+        let addr_c = Address::from([0xcc; 20]);
+
+        // Two accounts share the same loaded bytecode: the first should be
+        // written inline (CODE_INLINE), the second as a hash reference
+        // (CODE_HASH) into the first's blob.
+        db.insert_account_info(
+            addr_a,
+            AccountInfo {
+                balance: U256::from(1),
+                nonce: 1,
+                code_hash: shared_hash,
+                code: Some(shared_bytecode.clone()),
+            },
+        );
+        db.insert_account_info(
+            addr_b,
+            AccountInfo {
+                balance: U256::from(2),
+                nonce: 2,
+                code_hash: shared_hash,
+                code: Some(shared_bytecode.clone()),
+            },
+        );
+        // A codeless account.
+        db.insert_account_info(addr_c, AccountInfo::default());
+
+        db.cache
+            .accounts
+            .get_mut(&addr_a)
+            .unwrap()
+            .storage
+            .insert(U256::from(7), U256::from(42));
+
+        let mut buf = Vec::new();
+        export_snapshot(&db, &mut buf).unwrap();
+
+        let mut restored = new_db();
+        import_snapshot(&mut restored, &mut buf.as_slice()).unwrap();
+
+        for addr in [addr_a, addr_b] {
+            let original = db.cache.accounts.get(&addr).unwrap();
+            let round_tripped = restored.cache.accounts.get(&addr).unwrap();
+            assert_eq!(round_tripped.info.balance, original.info.balance);
+            assert_eq!(round_tripped.info.nonce, original.info.nonce);
+            assert_eq!(round_tripped.info.code_hash, shared_hash);
+            assert_eq!(
+                *round_tripped.info.code.as_ref().unwrap().bytes(),
+                Bytes::from(shared_code.clone())
+            );
+        }
+        assert_eq!(
+            restored.cache.accounts[&addr_a].storage[&U256::from(7)],
+            U256::from(42)
+        );
+        assert_eq!(
+            restored.cache.accounts[&addr_c].info.code_hash,
+            revm::primitives::KECCAK_EMPTY
+        );
+    }
+
+    #[test]
+    fn test_read_bytes_rejects_length_over_cap() {
+        let mut buf = Vec::new();
+        write_u64(&mut buf, MAX_CODE_LEN + 1).unwrap();
+        let err = read_bytes(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_import_snapshot_rejects_account_count_over_cap() {
+        let mut buf = Vec::new();
+        write_u64(&mut buf, MAX_ACCOUNTS + 1).unwrap();
+        let mut db = new_db();
+        let err = import_snapshot(&mut db, &mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,269 @@
+use revm::{
+    db::CacheDB,
+    primitives::{AccountInfo, Address, U256},
+    Database, DatabaseRef,
+};
+use std::collections::{HashMap, VecDeque};
+
+/// The historical default: the number of storage slots kept warm per account
+/// before least-recently-used eviction kicks in.
+pub const DEFAULT_STORAGE_CACHE_SIZE: usize = 8192;
+
+/// Hit/miss/eviction counters for [`CachedDb`], exposed so callers can tune
+/// `with_storage_cache_size`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// Per-account LRU order of clean (non-dirty) storage keys, most-recently-used
+/// at the back.
+#[derive(Default)]
+struct LruOrder {
+    order: VecDeque<U256>,
+}
+
+impl LruOrder {
+    fn touch(&mut self, key: U256) {
+        if let Some(pos) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(key);
+    }
+
+    fn remove(&mut self, key: U256) {
+        if let Some(pos) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(pos);
+        }
+    }
+
+    fn pop_lru(&mut self) -> Option<U256> {
+        self.order.pop_front()
+    }
+}
+
+/// A `CacheDB` wrapper that bounds the number of storage entries kept per
+/// account with a least-recently-used eviction policy, instead of the
+/// unbounded growth of a bare `CacheDB`.
+///
+/// Dirty (uncommitted) entries are never evicted, only tracked for LRU order
+/// once they become clean (e.g. after a commit writes them back).
+pub struct CachedDb<ExtDB> {
+    db: CacheDB<ExtDB>,
+    storage_cache_size: usize,
+    lru: HashMap<Address, LruOrder>,
+    dirty_storage: HashMap<Address, std::collections::HashSet<U256>>,
+    stats: CacheStats,
+}
+
+impl<ExtDB: DatabaseRef> CachedDb<ExtDB> {
+    pub fn new(db: CacheDB<ExtDB>) -> Self {
+        Self::with_storage_cache_size(db, DEFAULT_STORAGE_CACHE_SIZE)
+    }
+
+    /// Build a `CachedDb` that caps each account's *clean* storage entries at
+    /// `n`, evicting least-recently-used entries past that limit.
+    pub fn with_storage_cache_size(db: CacheDB<ExtDB>, n: usize) -> Self {
+        Self {
+            db,
+            storage_cache_size: n,
+            lru: HashMap::new(),
+            dirty_storage: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Mark a storage slot dirty (uncommitted), exempting it from eviction
+    /// until it is committed back to clean via [`Self::mark_clean`].
+    pub fn mark_dirty(&mut self, address: Address, key: U256) {
+        self.dirty_storage.entry(address).or_default().insert(key);
+        self.lru.entry(address).or_default().remove(key);
+    }
+
+    /// Mark a previously-dirty storage slot clean again, making it eligible
+    /// for LRU eviction.
+    pub fn mark_clean(&mut self, address: Address, key: U256) {
+        if let Some(dirty) = self.dirty_storage.get_mut(&address) {
+            dirty.remove(&key);
+        }
+        self.touch_and_evict(address, key);
+    }
+
+    fn touch_and_evict(&mut self, address: Address, key: U256) {
+        let is_dirty = self
+            .dirty_storage
+            .get(&address)
+            .map(|d| d.contains(&key))
+            .unwrap_or(false);
+        if is_dirty {
+            return;
+        }
+
+        let order = self.lru.entry(address).or_default();
+        order.touch(key);
+
+        let clean_len = order.order.len();
+        if clean_len > self.storage_cache_size {
+            if let Some(evicted) = order.pop_lru() {
+                if let Some(account) = self.db.cache.accounts.get_mut(&address) {
+                    account.storage.remove(&evicted);
+                }
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    /// Look up an account and update the hit/miss counters. This does not
+    /// touch the per-account storage LRU order.
+    pub fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, ExtDB::Error> {
+        let known = self.db.cache.accounts.contains_key(&address);
+        let result = self.db.basic(address);
+        if known {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        result
+    }
+
+    /// Load (or fetch) a storage value, recording the access in the LRU order
+    /// for this account and evicting the least-recently-used clean entry if
+    /// the cap is now exceeded.
+    pub fn storage(&mut self, address: Address, key: U256) -> Result<U256, ExtDB::Error> {
+        let account_known = self
+            .db
+            .cache
+            .accounts
+            .get(&address)
+            .map(|account| account.storage.contains_key(&key))
+            .unwrap_or(false);
+
+        let value = self.db.storage(address, key)?;
+
+        if account_known {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+
+        self.touch_and_evict(address, key);
+        Ok(value)
+    }
+
+    pub fn insert_account_info(&mut self, address: Address, info: AccountInfo) {
+        self.db.insert_account_info(address, info);
+    }
+
+    pub fn db(&self) -> &CacheDB<ExtDB> {
+        &self.db
+    }
+
+    pub fn db_mut(&mut self) -> &mut CacheDB<ExtDB> {
+        &mut self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::EmptyDB;
+
+    fn new_cached_db(cap: usize) -> CachedDb<EmptyDB> {
+        CachedDb::with_storage_cache_size(CacheDB::new(EmptyDB::default()), cap)
+    }
+
+    #[test]
+    fn test_lru_eviction_drops_oldest_entry_past_the_cap() {
+        let mut cache = new_cached_db(2);
+        let address = Address::from([0x44; 20]);
+        cache.insert_account_info(address, AccountInfo::default());
+
+        cache.storage(address, U256::from(1)).unwrap();
+        cache.storage(address, U256::from(2)).unwrap();
+        cache.storage(address, U256::from(3)).unwrap();
+
+        assert_eq!(cache.cache_stats().evictions, 1);
+        assert!(!cache
+            .db()
+            .cache
+            .accounts
+            .get(&address)
+            .unwrap()
+            .storage
+            .contains_key(&U256::from(1)));
+        assert!(cache
+            .db()
+            .cache
+            .accounts
+            .get(&address)
+            .unwrap()
+            .storage
+            .contains_key(&U256::from(2)));
+        assert!(cache
+            .db()
+            .cache
+            .accounts
+            .get(&address)
+            .unwrap()
+            .storage
+            .contains_key(&U256::from(3)));
+    }
+
+    #[test]
+    fn test_touching_an_entry_protects_it_from_eviction() {
+        let mut cache = new_cached_db(2);
+        let address = Address::from([0x55; 20]);
+        cache.insert_account_info(address, AccountInfo::default());
+
+        cache.storage(address, U256::from(1)).unwrap();
+        cache.storage(address, U256::from(2)).unwrap();
+        // Re-touch key 1 so key 2 becomes the least-recently-used entry.
+        cache.storage(address, U256::from(1)).unwrap();
+        cache.storage(address, U256::from(3)).unwrap();
+
+        assert!(cache
+            .db()
+            .cache
+            .accounts
+            .get(&address)
+            .unwrap()
+            .storage
+            .contains_key(&U256::from(1)));
+        assert!(!cache
+            .db()
+            .cache
+            .accounts
+            .get(&address)
+            .unwrap()
+            .storage
+            .contains_key(&U256::from(2)));
+    }
+
+    #[test]
+    fn test_dirty_entries_are_exempt_from_eviction() {
+        let mut cache = new_cached_db(1);
+        let address = Address::from([0x66; 20]);
+        cache.insert_account_info(address, AccountInfo::default());
+
+        cache.storage(address, U256::from(1)).unwrap();
+        cache.mark_dirty(address, U256::from(1));
+        cache.storage(address, U256::from(2)).unwrap();
+        cache.storage(address, U256::from(3)).unwrap();
+
+        assert_eq!(cache.cache_stats().evictions, 1);
+        assert!(cache
+            .db()
+            .cache
+            .accounts
+            .get(&address)
+            .unwrap()
+            .storage
+            .contains_key(&U256::from(1)));
+    }
+}
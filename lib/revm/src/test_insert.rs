@@ -1,3 +1,9 @@
+mod diff;
+mod dirty;
+mod journal;
+mod snapshot;
+mod storage_cache;
+
 use revm::{
     db::{CacheDB, EmptyDB},
     primitives::{Address, AccountInfo, Bytecode, Bytes, U256},